@@ -8,6 +8,8 @@ use std::path::Path;
 use std::sync::LazyLock;
 
 const WORDS_FILE_CONTENT: &str = include_str!("./dict.txt");
+const GLOSS_FILE_CONTENT: &str = include_str!("./gloss.txt");
+const EMOSI_FILE_CONTENT: &str = include_str!("./emosi.txt");
 
 fn main() {
     let out_dir = env::var_os("OUT_DIR").unwrap();
@@ -31,6 +33,14 @@ fn generate(out: &mut impl Write) -> io::Result<()> {
 
     writeln!(out, "impl Word {{")?;
 
+    write!(out, "    pub const ALL: &[Word] = &[")?;
+    for (_, word) in words_iter() {
+        write!(out, "Self::{},", capitalize(word))?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
     write!(out, "    const SITELEN_CHAR: &[char] = &[")?;
     for (value, _) in words_iter() {
         write!(out, "'{}',", char::from_u32(value).unwrap())?;
@@ -47,6 +57,58 @@ fn generate(out: &mut impl Write) -> io::Result<()> {
 
     writeln!(out)?;
 
+    write!(out, "    const BOOK: &[Book] = &[")?;
+    for entry in entries() {
+        write!(out, "Book::{},", variant(entry.book))?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
+    write!(out, "    const UCSUR_VERSION: &[u16] = &[")?;
+    for entry in entries() {
+        write!(out, "{},", entry.ucsur_version)?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
+    write!(out, "    const STATUS: &[Status] = &[")?;
+    for entry in entries() {
+        write!(out, "Status::{},", variant(entry.status))?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
+    write!(out, "    const GLOSS: &[&str] = &[")?;
+    for (_, word) in words_iter() {
+        write!(out, "{:?},", lookup(GLOSS_FILE_CONTENT, word))?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
+    write!(out, "    const EMOSI: &[&str] = &[")?;
+    for (_, word) in words_iter() {
+        write!(out, "{:?},", lookup(EMOSI_FILE_CONTENT, word))?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
+    write!(out, "    const PREFIXES: &[&[&str]] = &[")?;
+    for (_, word) in words_iter() {
+        write!(out, "&[")?;
+        for prefix in word_prefixes(word) {
+            write!(out, "\"{prefix}\",")?;
+        }
+        write!(out, "],")?;
+    }
+    writeln!(out, "];")?;
+
+    writeln!(out)?;
+
     writeln!(out, "    pub const fn as_sitelen(self) -> char {{")?;
     writeln!(out, "        Self::SITELEN_CHAR[self as usize]")?;
     writeln!(out, "    }}")?;
@@ -54,6 +116,36 @@ fn generate(out: &mut impl Write) -> io::Result<()> {
     writeln!(out, "    pub const fn as_lasina(self) -> &'static str {{")?;
     writeln!(out, "        Self::LASINA_WORD[self as usize]")?;
     writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    // the book that the word comes from")?;
+    writeln!(out, "    pub const fn book(self) -> Book {{")?;
+    writeln!(out, "        Self::BOOK[self as usize]")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    // the year of the UCSUR version that the codepoint of the glyph was assigned in")?;
+    writeln!(out, "    pub const fn ucsur_version(self) -> u16 {{")?;
+    writeln!(out, "        Self::UCSUR_VERSION[self as usize]")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    pub const fn status(self) -> Status {{")?;
+    writeln!(out, "        Self::STATUS[self as usize]")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    // the English meanings of the word, separated by commas")?;
+    writeln!(out, "    pub const fn gloss(self) -> &'static str {{")?;
+    writeln!(out, "        Self::GLOSS[self as usize]")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    // the emoji of the word in sitelen Emosi")?;
+    writeln!(out, "    pub const fn as_emosi(self) -> &'static str {{")?;
+    writeln!(out, "        Self::EMOSI[self as usize]")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    // the part of the word that the glyph stands for inside of a cartouche when it's")?;
+    writeln!(out, "    // followed by the given amount of middle dots")?;
+    writeln!(out, "    pub fn prefix(self, dots: usize) -> Option<&'static str> {{")?;
+    writeln!(out, "        Self::PREFIXES[self as usize].get(dots).copied()")?;
+    writeln!(out, "    }}")?;
 
     writeln!(out)?;
 
@@ -130,8 +222,17 @@ fn capitalize(input: &str) -> String {
     upper
 }
 
-fn words_iter() -> impl Iterator<Item = (u32, &'static str)> + 'static {
-    static WORDS: LazyLock<Vec<(u32, &'static str)>> = LazyLock::new(|| {
+// a line of the dictionary: the codepoint, the word, the book, the UCSUR version and the status
+struct Entry {
+    value: u32,
+    name: &'static str,
+    book: &'static str,
+    ucsur_version: u16,
+    status: &'static str,
+}
+
+fn entries() -> impl Iterator<Item = &'static Entry> + 'static {
+    static ENTRIES: LazyLock<Vec<Entry>> = LazyLock::new(|| {
         WORDS_FILE_CONTENT
             .lines()
             .map(|line| {
@@ -143,12 +244,42 @@ fn words_iter() -> impl Iterator<Item = (u32, &'static str)> + 'static {
                     })
                     .unwrap();
                 let name = iter.next().unwrap();
-                (value, name)
+                let book = iter.next().unwrap();
+                let ucsur_version = iter.next().and_then(|version| version.parse().ok()).unwrap();
+                let status = iter.next().unwrap();
+                Entry {
+                    value,
+                    name,
+                    book,
+                    ucsur_version,
+                    status,
+                }
             })
             .collect()
     });
 
-    WORDS.iter().copied()
+    ENTRIES.iter()
+}
+
+fn words_iter() -> impl Iterator<Item = (u32, &'static str)> + 'static {
+    entries().map(|entry| (entry.value, entry.name))
+}
+
+// the name of the enum variant for a value of the dictionary, e.g. `ku-suli` is `KuSuli`
+fn variant(value: &str) -> String {
+    value.split('-').map(capitalize).collect()
+}
+
+// the rest of the line of the word in a file that has a line for every word of the dictionary,
+// like the glosses and the emoji
+fn lookup(content: &'static str, word: &str) -> &'static str {
+    content
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(' ')?;
+            (name == word).then_some(value)
+        })
+        .unwrap_or_else(|| panic!("no line for `{word}`"))
 }
 
 struct MorasIter<'a> {
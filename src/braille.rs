@@ -0,0 +1,337 @@
+use std::io;
+
+use crate::convert::{Decoded, decode};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{self, Location, Span, Spanned, Token};
+use crate::punctuation::Punct;
+use crate::show::Style;
+
+// dots 6, before a capital letter
+const CAPITAL: char = '⠠';
+
+// dots 3456, before the digits of a number
+const NUMBER: char = '⠼';
+
+// U+2800 BRAILLE PATTERN BLANK, between words
+const SPACE: char = '⠀';
+
+// the letters, as in Unified English Braille
+pub fn letter(c: char) -> Option<char> {
+    Some(match c.to_ascii_lowercase() {
+        'a' => '⠁',
+        'b' => '⠃',
+        'c' => '⠉',
+        'd' => '⠙',
+        'e' => '⠑',
+        'f' => '⠋',
+        'g' => '⠛',
+        'h' => '⠓',
+        'i' => '⠊',
+        'j' => '⠚',
+        'k' => '⠅',
+        'l' => '⠇',
+        'm' => '⠍',
+        'n' => '⠝',
+        'o' => '⠕',
+        'p' => '⠏',
+        'q' => '⠟',
+        'r' => '⠗',
+        's' => '⠎',
+        't' => '⠞',
+        'u' => '⠥',
+        'v' => '⠧',
+        'w' => '⠺',
+        'x' => '⠭',
+        'y' => '⠽',
+        'z' => '⠵',
+        _ => return None,
+    })
+}
+
+// the digits are the letters from `a` to `j`
+fn digit(c: char) -> Option<char> {
+    Some(match c {
+        '1' => '⠁',
+        '2' => '⠃',
+        '3' => '⠉',
+        '4' => '⠙',
+        '5' => '⠑',
+        '6' => '⠋',
+        '7' => '⠛',
+        '8' => '⠓',
+        '9' => '⠊',
+        '0' => '⠚',
+        _ => return None,
+    })
+}
+
+fn punct(punct: Punct) -> &'static str {
+    match punct {
+        Punct::FullStop => "⠲",
+        Punct::Comma => "⠂",
+        Punct::Exclamation => "⠖",
+        Punct::Question => "⠦",
+    }
+}
+
+// the symbols of the modifiers, as in Unified English Braille
+fn modifier(token: Token) -> Option<&'static str> {
+    Some(match token {
+        Token::LParen => "⠐⠣",
+        Token::RParen => "⠐⠜",
+        Token::LBrace => "⠸⠣",
+        Token::RBrace => "⠸⠜",
+        Token::Plus => "⠐⠖",
+        Token::Minus => "⠤",
+        Token::Underscore => "⠨⠤",
+        Token::Colon => "⠒",
+        Token::Te => "⠘⠦",
+        Token::To => "⠘⠴",
+
+        // the middle dots of names and the alternative forms of glyphs have nothing to show
+        Token::Dot | Token::AltSymbol => "",
+        _ => return None,
+    })
+}
+
+// the words with their letters in braille
+fn write_letters(out: &mut impl io::Write, text: &str) -> io::Result<()> {
+    text.chars().try_for_each(|c| write!(out, "{}", letter(c).unwrap_or(c)))
+}
+
+// whether the character of text that isn't converted has a braille form
+fn transcribable(c: char) -> bool {
+    letter(c).is_some() || digit(c).is_some() || matches!(c, '"' | '\'') || c.is_whitespace()
+}
+
+// writes the text that isn't converted, with capital letters, digits, ASCII quotes, which open
+// and close by turns, and apostrophes. everything else is kept as is, and is reported by `check`.
+// `in_number` is whether the text right before this one ended in a digit
+fn write_text(out: &mut impl io::Write, text: &str, in_quote: &mut bool, in_number: &mut bool) -> io::Result<()> {
+    for c in text.chars() {
+        match c {
+            _ if let Some(digit) = digit(c) => {
+                if !*in_number {
+                    write!(out, "{NUMBER}")?;
+                }
+
+                write!(out, "{digit}")?;
+            }
+            '"' => {
+                write!(out, "{}", if *in_quote { "⠘⠴" } else { "⠘⠦" })?;
+                *in_quote = !*in_quote;
+            }
+            '\'' => write!(out, "⠄")?,
+            _ if c.is_ascii_uppercase() => write!(out, "{CAPITAL}{}", letter(c).unwrap_or(c))?,
+            _ => write!(out, "{}", letter(c).unwrap_or(c))?,
+        }
+
+        *in_number = c.is_ascii_digit();
+    }
+
+    Ok(())
+}
+
+// whether a space between the tokens is dropped, since quotes and punctuation are written right
+// next to the words they're around
+fn drops_space(prev: Option<&Token>, next: &Token) -> bool {
+    matches!(prev, None | Some(Token::Te | Token::Newline(..)))
+        || matches!(next, Token::To | Token::Punct(..) | Token::Newline(..))
+}
+
+fn is_text(token: &Token) -> bool {
+    matches!(token, Token::Other(..) | Token::Newline(..))
+}
+
+// writes the tokens in Unicode braille. the spaces between them come from the tokens themselves,
+// since sitelen UCSUR has no spaces between glyphs: a word is separated from the one before it
+// unless either of them is written right next to the other, and only text that isn't converted
+// keeps the spaces around it as they are
+fn write_tokens<'a>(out: &mut impl io::Write, tokens: impl Iterator<Item = Token<'a>>) -> io::Result<()> {
+    let mut prev: Option<Token> = None;
+    let mut spaces = false;
+    let mut in_quote = false;
+    let mut in_number = false;
+    for token in tokens {
+        if let Token::Space(..) = token {
+            spaces = true;
+            in_number = false;
+            continue;
+        }
+
+        let separated = match prev {
+            Some(prev) if is_text(&prev) || is_text(&token) => spaces && !matches!(prev, Token::Newline(..)),
+            prev => {
+                prev.is_some_and(|prev| !prev.attaches_right() && !token.attaches_left())
+                    && !drops_space(prev.as_ref(), &token)
+            }
+        };
+
+        if separated && !matches!(token, Token::Newline(..)) {
+            write!(out, "{SPACE}")?;
+        }
+
+        match token {
+            Token::Word(word) => write_letters(out, word.as_lasina())?,
+            Token::Lasina(name) => {
+                write!(out, "{CAPITAL}")?;
+                write_letters(out, name)?;
+            }
+            Token::Number(digits) => {
+                write!(out, "{NUMBER}")?;
+                digits.chars().try_for_each(|c| write!(out, "{}", digit(c).unwrap_or(c)))?;
+            }
+            Token::Punct(p) => write!(out, "{}", punct(p))?,
+            Token::LBrack | Token::RBrack | Token::Space(..) => {}
+            Token::Newline(newlines) => write!(out, "{newlines}")?,
+            Token::Other(other) => write_text(out, other, &mut in_quote, &mut in_number)?,
+            token => write!(out, "{}", modifier(token).unwrap_or_default())?,
+        }
+
+        if !matches!(token, Token::Other(..)) {
+            in_number = false;
+        }
+
+        prev = Some(token);
+        spaces = false;
+    }
+
+    Ok(())
+}
+
+// writes the sitelen Lasina input in Unicode braille. names, with or without a cartouche, are
+// written as capitalized words, and the cartouche itself isn't written
+pub fn to_braille(out: &mut impl io::Write, input: &str, style: Style) -> io::Result<()> {
+    write_tokens(out, lexer::tokens_in(input, style.dialect))
+}
+
+// same as `to_braille`, for sitelen UCSUR input. it's read back into the same tokens as
+// sitelen Lasina, so both give the same braille
+pub fn sitelen_to_braille(out: &mut impl io::Write, input: &str, style: Style) -> io::Result<()> {
+    let decoded = decode(input, style);
+    let tokens = decoded.iter().map(|decoded| match decoded {
+        Decoded::Token(token) => *token,
+        Decoded::Name(name) => Token::Lasina(name),
+        Decoded::Number(digits) => Token::Number(digits),
+        Decoded::Space(spaces) => Token::Space(spaces),
+        Decoded::Text(text) if text.contains(['\r', '\n']) => Token::Newline(text),
+        Decoded::Text(text) => Token::Other(text),
+    });
+
+    write_tokens(out, tokens)
+}
+
+fn untranscribable(text: &str, start: Location) -> impl Iterator<Item = Diagnostic> + '_ {
+    text.char_indices().filter(|&(_, c)| !transcribable(c)).map(move |(i, c)| {
+        let char_start = start.advance(&text[..i]);
+        Diagnostic {
+            code: "no-braille",
+            span: Span {
+                start: char_start,
+                end: char_start.advance(&text[i..i + c.len_utf8()]),
+            },
+            message: format!("`{c}` can't be written in braille"),
+            help: None,
+        }
+    })
+}
+
+// reports every character of the sitelen Lasina input, which starts at the given location, that
+// has no braille form
+pub fn check(input: &str, start: Location, style: Style) -> Vec<Diagnostic> {
+    lexer::spanned_tokens_in(input, start, style.dialect)
+        .flat_map(|Spanned { token, span }| match token {
+            Token::Other(other) => Some(untranscribable(other, span.start)),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+// same as `check`, for sitelen UCSUR input
+pub fn check_sitelen(input: &str, start: Location, style: Style) -> Vec<Diagnostic> {
+    decode(input, style)
+        .into_iter()
+        .flat_map(|decoded| match decoded {
+            Decoded::Text(text) => {
+                let offset = text.as_ptr() as usize - input.as_ptr() as usize;
+                Some(untranscribable(text, start.advance(&input[..offset])))
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::number::NumberSystem;
+
+    fn braille(input: &str, style: Style) -> String {
+        let mut output = Vec::new();
+        to_braille(&mut output, input, style).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    // the braille of the input after it's converted to sitelen UCSUR
+    fn sitelen_braille(input: &str, style: Style) -> String {
+        let mut sitelen = Vec::new();
+        to_sitelen(&mut sitelen, input, style, None).unwrap();
+
+        let mut output = Vec::new();
+        sitelen_to_braille(&mut output, &String::from_utf8(sitelen).unwrap(), style).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn words_and_names() {
+        assert_eq!(braille("toki-pona", Style::default()), "⠞⠕⠅⠊⠤⠏⠕⠝⠁");
+        assert_eq!(braille("jan [Sonja] li toki.", Style::default()), "⠚⠁⠝⠀⠠⠎⠕⠝⠚⠁⠀⠇⠊⠀⠞⠕⠅⠊⠲");
+        assert_eq!(braille("te toki to li 25", Style::default()), "⠘⠦⠞⠕⠅⠊⠘⠴⠀⠇⠊⠀⠼⠃⠑");
+    }
+
+    #[test]
+    fn both_directions_agree() {
+        let pu = Style {
+            numbers: Some(NumberSystem::Pu),
+            ..Style::default()
+        };
+
+        let cases = [
+            ("toki-pona", Style::default()),
+            ("jan [Sonja] li toki-pona.", Style::default()),
+            ("jan Sonja li toki e toki pona!", Style::default()),
+            ("te toki to li pona, anu seme?", Style::default()),
+            ("pi(toki ala) li {toki}la", Style::default()),
+            ("toki^ pona+ala", Style::default()),
+            ("mi jo e kili \"3\"", pu),
+            ("mi\nsina\n", Style::default()),
+        ];
+
+        for (input, style) in cases {
+            assert_eq!(sitelen_braille(input, style), braille(input, style), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn text_that_isnt_converted() {
+        assert_eq!(braille("jan \"Bob\" li xyz", Style::default()), "⠚⠁⠝⠀⠘⠦⠠⠃⠕⠃⠘⠴⠀⠇⠊⠀⠭⠽⠵");
+
+        // digits that are left in sitelen UCSUR are still numbers
+        assert_eq!(sitelen_braille("\u{f1911} 25", Style::default()), "⠚⠁⠝⠀⠼⠃⠑");
+        assert_eq!(sitelen_braille("jan \"Bob\" li xyz", Style::default()), "⠚⠁⠝⠀⠘⠦⠠⠃⠕⠃⠘⠴⠀⠇⠊⠀⠭⠽⠵");
+    }
+
+    #[test]
+    fn characters_without_braille_are_reported() {
+        let codes = |diagnostics: Vec<Diagnostic>| -> Vec<String> {
+            diagnostics.iter().map(|diagnostic| format!("{} {}", diagnostic.code, diagnostic.span.start)).collect()
+        };
+
+        assert_eq!(codes(check("jan \"Bob\" li pona", Location::START, Style::default())), [] as [String; 0]);
+        assert_eq!(codes(check("jan <3\nli @", Location::START, Style::default())), ["no-braille 1:5", "no-braille 2:4"]);
+        assert_eq!(codes(check_sitelen("\u{f1911} <3\n\u{f1927} @", Location::START, Style::default())), ["no-braille 1:3", "no-braille 2:3"]);
+    }
+}
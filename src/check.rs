@@ -0,0 +1,167 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{Location, Span};
+use crate::modifier::{Modifier, VARIATION_SELECTORS};
+use crate::word::Word;
+
+// codepoints inside of the sitelen pona block that "shall not be used"
+const UNASSIGNED: [std::ops::RangeInclusive<char>; 3] = [
+    '\u{f1989}'..='\u{f198f}',
+    '\u{f199e}'..='\u{f199f}',
+    '\u{f19a5}'..='\u{f19ff}',
+];
+
+// finds the structural problems of sitelen UCSUR text, e.g. a cartouche that is never closed
+pub fn check(input: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut report = |code, span, message: String| {
+        diagnostics.push(Diagnostic {
+            code,
+            span,
+            message,
+            help: None,
+        })
+    };
+
+    // the opening modifiers that haven't been closed yet
+    let mut open: Vec<(Modifier, Span)> = Vec::new();
+
+    // whether the previous character is a glyph, optionally followed by variation selectors
+    let mut prev_is_glyph = false;
+
+    let mut location = Location::START;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let start = location;
+        location = location.advance(c.encode_utf8(&mut [0; 4]));
+        let span = Span { start, end: location };
+
+        let name = match Modifier::from_sitelen(c) {
+            Some(modifier) => format!("U+{:X} {}", u32::from(c), modifier.name()),
+            None => format!("U+{:X}", u32::from(c)),
+        };
+
+        match Modifier::from_sitelen(c) {
+            Some(Modifier::StackingJoiner | Modifier::ScalingJoiner) => {
+                if start.column == 1 {
+                    report("joiner-at-line-start", span, format!("{name} is at the start of a line"));
+                } else if !prev_is_glyph {
+                    report("dangling-joiner", span, format!("{name} has no glyph before it"));
+                }
+
+                if chars.peek().is_none_or(|&c| Word::from_sitelen(c).is_none()) {
+                    report("dangling-joiner", span, format!("{name} has no glyph after it"));
+                }
+            }
+
+            Some(
+                modifier @ (Modifier::StartOfCartouche
+                | Modifier::StartOfLongGlyph
+                | Modifier::StartOfReverseLongGlyph),
+            ) => open.push((modifier, span)),
+
+            Some(
+                modifier @ (Modifier::EndOfCartouche
+                | Modifier::EndOfLongGlyph
+                | Modifier::EndOfReverseLongGlyph),
+            ) => {
+                let start = opening(modifier);
+                if open.last().is_some_and(|&(open, _)| open == start) {
+                    open.pop();
+                } else {
+                    let message = format!("{name} has no {} before it", start.name());
+                    report("unbalanced-delimiter", span, message);
+                }
+            }
+
+            _ => {}
+        }
+
+        if VARIATION_SELECTORS.contains(&c) && !prev_is_glyph {
+            let message = format!("variation selector {name} doesn't follow a glyph");
+            report("misplaced-variation-selector", span, message);
+        }
+
+        if UNASSIGNED.iter().any(|range| range.contains(&c)) {
+            report("unassigned-codepoint", span, format!("{name} is not assigned"));
+        }
+
+        if !VARIATION_SELECTORS.contains(&c) {
+            prev_is_glyph = Word::from_sitelen(c).is_some();
+        }
+    }
+
+    for (modifier, span) in open {
+        let message = format!(
+            "U+{:X} {} is never closed",
+            u32::from(modifier.as_sitelen()),
+            modifier.name()
+        );
+        report("unbalanced-delimiter", span, message);
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start.offset);
+    diagnostics
+}
+
+fn opening(end: Modifier) -> Modifier {
+    match end {
+        Modifier::EndOfCartouche => Modifier::StartOfCartouche,
+        Modifier::EndOfLongGlyph => Modifier::StartOfLongGlyph,
+        Modifier::EndOfReverseLongGlyph => Modifier::StartOfReverseLongGlyph,
+        modifier => modifier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKI: char = Word::Toki.as_sitelen();
+    const PONA: char = Word::Pona.as_sitelen();
+    const JOINER: char = Modifier::StackingJoiner.as_sitelen();
+    const START: char = Modifier::StartOfCartouche.as_sitelen();
+    const END: char = Modifier::EndOfCartouche.as_sitelen();
+
+    // the code and the `line:column` of every diagnostic
+    fn codes(input: &str) -> Vec<(&'static str, String)> {
+        check(input)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.span.start.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_input_has_no_diagnostics() {
+        assert_eq!(codes(&format!("{TOKI}{JOINER}{PONA}\u{fe00} {START}{TOKI}{END}\n")), []);
+    }
+
+    #[test]
+    fn joiners() {
+        assert_eq!(codes(&format!("{JOINER}{PONA}")), [("joiner-at-line-start", "1:1".into())]);
+        assert_eq!(codes(&format!("{TOKI}{JOINER}")), [("dangling-joiner", "1:2".into())]);
+        assert_eq!(codes(&format!("{TOKI} {JOINER}{PONA}")), [("dangling-joiner", "1:3".into())]);
+
+        // a variation selector doesn't separate the joiner from its glyph
+        assert_eq!(codes(&format!("{TOKI}\u{fe00}{JOINER}{PONA}")), []);
+    }
+
+    #[test]
+    fn unbalanced_delimiters() {
+        assert_eq!(codes(&format!("{TOKI}{END}")), [("unbalanced-delimiter", "1:2".into())]);
+        assert_eq!(codes(&format!("\n{START}{TOKI}")), [("unbalanced-delimiter", "2:1".into())]);
+    }
+
+    #[test]
+    fn misplaced_variation_selectors() {
+        assert_eq!(codes("a\u{fe00}"), [("misplaced-variation-selector", "1:2".into())]);
+        assert_eq!(codes(&format!("{START}\u{fe01}")).len(), 2);
+    }
+
+    #[test]
+    fn unassigned_codepoints() {
+        assert_eq!(codes("\u{f1989}\u{f19a5}"), [
+            ("unassigned-codepoint", "1:1".into()),
+            ("unassigned-codepoint", "1:2".into()),
+        ]);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::modifier::{Modifier, VARIATION_SELECTORS};
+use crate::word::Word;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterKind {
+    // a glyph together with its variation selectors and combining extensions
+    Glyph,
+
+    // glyphs connected with STACKING JOINER or SCALING JOINER
+    Joined,
+
+    // everything from START OF CARTOUCHE to END OF CARTOUCHE
+    Cartouche,
+
+    // everything from START OF LONG GLYPH to END OF LONG GLYPH, together with the glyph before
+    // it, or from START OF REVERSE LONG GLYPH to END OF REVERSE LONG GLYPH, together with the
+    // glyph after it
+    LongGlyph,
+
+    // a single character of anything else, or a CRLF
+    Other,
+}
+
+// a piece of sitelen UCSUR text that is shown, and should be edited, as a single unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cluster<'a> {
+    pub kind: ClusterKind,
+    pub text: &'a str,
+
+    // in bytes, from the start of the text that was segmented
+    pub offset: usize,
+}
+
+pub fn clusters(input: &str) -> impl Iterator<Item = Cluster<'_>> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let rest = &input[offset..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let (kind, len) = next_cluster(rest);
+        let cluster = Cluster {
+            kind,
+            text: &rest[..len],
+            offset,
+        };
+
+        offset += len;
+        Some(cluster)
+    })
+}
+
+// the kind and the length in bytes of the cluster at the start of the input
+fn next_cluster(input: &str) -> (ClusterKind, usize) {
+    let first = input.chars().next().unwrap_or_default();
+
+    let end = match Modifier::from_sitelen(first) {
+        Some(Modifier::StartOfCartouche) => Some((ClusterKind::Cartouche, Modifier::EndOfCartouche)),
+        Some(Modifier::StartOfLongGlyph) => Some((ClusterKind::LongGlyph, Modifier::EndOfLongGlyph)),
+        Some(Modifier::StartOfReverseLongGlyph) => {
+            Some((ClusterKind::LongGlyph, Modifier::EndOfReverseLongGlyph))
+        }
+        _ => None,
+    };
+
+    if let Some((kind, end)) = end {
+        let (mut len, closed) = block_len(input, end);
+
+        // the glyph that a reverse long glyph belongs to comes after it
+        if closed
+            && end == Modifier::EndOfReverseLongGlyph
+            && input[len..].chars().next().and_then(Word::from_sitelen).is_some()
+        {
+            len += glyph_len(&input[len..]);
+        }
+
+        return (kind, len);
+    }
+
+    if Word::from_sitelen(first).is_some() {
+        let (mut kind, mut len) = joined_len(input);
+
+        // the glyph that a long glyph belongs to comes before it
+        if input[len..].starts_with(Modifier::StartOfLongGlyph.as_sitelen()) {
+            len += block_len(&input[len..], Modifier::EndOfLongGlyph).0;
+            kind = ClusterKind::LongGlyph;
+        }
+
+        return (kind, len);
+    }
+
+    if input.starts_with("\r\n") {
+        return (ClusterKind::Other, 2);
+    }
+
+    (ClusterKind::Other, first.len_utf8())
+}
+
+// the length in bytes of the block at the start of the input, which starts with its opening
+// modifier, and whether it's closed. an unclosed block ends with its line
+fn block_len(input: &str, end: Modifier) -> (usize, bool) {
+    input
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == end.as_sitelen() || c == '\n')
+        .map_or((input.len(), false), |(i, c)| {
+            if c == '\n' { (i, false) } else { (i + c.len_utf8(), true) }
+        })
+}
+
+// the kind and the length in bytes of the glyph at the start of the input, together with the
+// glyphs that are joined to it
+fn joined_len(input: &str) -> (ClusterKind, usize) {
+    let mut len = glyph_len(input);
+    let mut kind = ClusterKind::Glyph;
+
+    // a joiner only joins when there's a glyph after it
+    while let Some(joiner) = input[len..].chars().next()
+        && matches!(
+            Modifier::from_sitelen(joiner),
+            Some(Modifier::StackingJoiner | Modifier::ScalingJoiner)
+        )
+        && let after = &input[len + joiner.len_utf8()..]
+        && after.chars().next().and_then(Word::from_sitelen).is_some()
+    {
+        len += joiner.len_utf8() + glyph_len(after);
+        kind = ClusterKind::Joined;
+    }
+
+    (kind, len)
+}
+
+// the length in bytes of the glyph at the start of the input, together with the variation
+// selectors and the combining extensions that follow it
+fn glyph_len(input: &str) -> usize {
+    let mut chars = input.char_indices();
+    chars.next();
+    chars
+        .find(|&(_, c)| {
+            !VARIATION_SELECTORS.contains(&c)
+                && c != Modifier::CombiningLongGlyphExtension.as_sitelen()
+        })
+        .map_or(input.len(), |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::show::Style;
+
+    fn sitelen(input: &str) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, Style::default(), None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn kinds(input: &str) -> Vec<(ClusterKind, usize)> {
+        clusters(&sitelen(input)).map(|cluster| (cluster.kind, cluster.text.chars().count())).collect()
+    }
+
+    #[test]
+    fn glyphs_keep_their_selectors_and_joined_glyphs() {
+        assert_eq!(kinds("toki^ pona"), [(ClusterKind::Glyph, 2), (ClusterKind::Glyph, 1)]);
+        assert_eq!(kinds("toki-pona+ala"), [(ClusterKind::Joined, 5)]);
+
+        // a joiner with no glyph after it doesn't join
+        assert_eq!(kinds("toki-")[0], (ClusterKind::Glyph, 1));
+    }
+
+    #[test]
+    fn long_glyphs() {
+        assert_eq!(kinds("(toki ala)"), [(ClusterKind::LongGlyph, 4)]);
+
+        // the glyph before START OF LONG GLYPH is the one the long glyph belongs to
+        assert_eq!(kinds("pi(toki ala)"), [(ClusterKind::LongGlyph, 5)]);
+        assert_eq!(kinds("mi pi(toki ala)")[1], (ClusterKind::LongGlyph, 5));
+
+        // and the glyph after END OF REVERSE LONG GLYPH
+        assert_eq!(kinds("{toki}la"), [(ClusterKind::LongGlyph, 4)]);
+    }
+
+    #[test]
+    fn cartouches() {
+        assert_eq!(kinds("jan [Sonja] li")[1].0, ClusterKind::Cartouche);
+
+        // an unclosed block ends with its line
+        let text = sitelen("[toki\npona");
+        let clusters: Vec<_> = clusters(&text).map(|cluster| cluster.kind).collect();
+        assert_eq!(clusters, [ClusterKind::Cartouche, ClusterKind::Other, ClusterKind::Glyph]);
+    }
+
+    #[test]
+    fn offsets_cover_the_text() {
+        let text = sitelen("mi pi(toki ala)\r\n[Sonja] <3");
+        let mut offset = 0;
+        for cluster in clusters(&text) {
+            assert_eq!(cluster.offset, offset);
+            offset += cluster.text.len();
+        }
+
+        assert_eq!(offset, text.len());
+    }
+}
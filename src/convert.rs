@@ -0,0 +1,384 @@
+use std::io;
+
+use crate::lexer::{self, Location, Spanned, Token};
+use crate::modifier::Modifier;
+use crate::number::NumberSystem;
+use crate::show::{self, Style};
+use crate::source_map::SourceMap;
+use crate::spacing::Spacing;
+use crate::word::{Word, read_name};
+
+// when a source map is given, the tokens of the input are added to it, continuing from where it
+// ended, so a text can be converted in parts (e.g. line by line) into a single source map
+pub fn to_sitelen(
+    out: &mut impl io::Write,
+    input: &str,
+    style: Style,
+    mut source_map: Option<&mut SourceMap>,
+) -> io::Result<()> {
+    let start = source_map.as_ref().map_or(Location::START, |map| map.input_end());
+    for (Spanned { span, .. }, output) in converted_tokens(input, start, style) {
+        out.write_all(output.as_bytes())?;
+
+        if let Some(source_map) = source_map.as_deref_mut() {
+            source_map.push(span, output.len());
+        }
+    }
+
+    Ok(())
+}
+
+// every token of the input, which starts at the given location, together with its output
+pub fn converted_tokens(
+    input: &str,
+    start: Location,
+    style: Style,
+) -> impl Iterator<Item = (Spanned<'_>, String)> {
+    let mut tokens = lexer::spanned_tokens_in(input, start, style.dialect).peekable();
+    let mut encoder = Encoder::new(style);
+    std::iter::from_fn(move || {
+        let spanned = tokens.next()?;
+        let output = encoder.encode(spanned.token, tokens.peek().map(|next| &next.token));
+        Some((spanned, output))
+    })
+}
+
+// turns tokens into sitelen UCSUR, keeping track of the surroundings that spaces depend on
+pub struct Encoder {
+    style: Style,
+    prev_is_word: bool,
+
+    // whether the previous token is written as number words
+    prev_is_number: bool,
+
+    // how deep inside explicit cartouches the current token is
+    cartouche_depth: usize,
+
+    // the word right before the current token, which the alternative form belongs to
+    prev_word: Option<Word>,
+}
+
+impl Encoder {
+    pub const fn new(style: Style) -> Self {
+        Self {
+            style,
+            prev_is_word: false,
+            prev_is_number: false,
+            cartouche_depth: 0,
+            prev_word: None,
+        }
+    }
+
+    // the token that comes after the given one is needed for deciding whether to keep spaces
+    pub fn encode(&mut self, token: Token, next: Option<&Token>) -> String {
+        let style = self.style;
+        let is_text = |token: &Token| match token {
+            Token::Other(..) | Token::Punct(..) | Token::Newline(..) => true,
+            Token::Number(digits) => style.number_words(digits).is_none(),
+            Token::Word(word) => style.is_lasina(*word),
+            Token::Lasina(..) => !style.modifiers.spells_names(),
+
+            // modifiers that are written in ASCII
+            Token::LBrack | Token::RBrack => !style.modifiers.spells_names(),
+            _ => !style.modifiers.contains(*token),
+        };
+
+        let is_number = |token: &Token| match (token, style.numbers) {
+            (Token::Number(digits), Some(..)) => style.number_words(digits).is_some(),
+            (Token::Word(word), Some(system)) => system.is_number_word(*word),
+            _ => false,
+        };
+
+        let output = match token {
+            Token::Space(spaces) if matches!(style.spacing, Spacing::Double | Spacing::None) => {
+                // keep the spaces that separate the glyphs from text that isn't converted, and
+                // the spaces between numbers so they aren't read back as a single number
+                if !self.prev_is_word
+                    || next.is_none_or(is_text)
+                    || (self.prev_is_number && next.is_some_and(is_number))
+                {
+                    spaces.to_string()
+                } else {
+                    token.styled(style).to_string()
+                }
+            }
+
+            // names are written in sitelen Lasina, so their cartouches are kept as brackets
+            Token::LBrack | Token::RBrack if !style.modifiers.spells_names() => {
+                token.as_literal().to_string()
+            }
+
+            // the cartouche is already there
+            Token::Lasina(name) if self.cartouche_depth > 0 => self.construct_name(name),
+            Token::Word(word) if self.cartouche_depth > 0 && !style.words.contains(word) => {
+                self.construct_name(word.as_lasina())
+            }
+
+            Token::AltSymbol if self.prev_word.is_some_and(|word| !style.alternatives.contains(word)) => {
+                String::new()
+            }
+
+            _ => token.styled(style).to_string(),
+        };
+
+        if token != Token::AltSymbol {
+            self.prev_word = match token {
+                Token::Word(word) => Some(word),
+                _ => None,
+            };
+        }
+
+        match token {
+            Token::Word(..) | Token::Lasina(..) => self.prev_is_word = !is_text(&token),
+            Token::Other(..) | Token::Punct(..) | Token::Newline(..) => self.prev_is_word = false,
+            Token::Number(digits) => self.prev_is_word = style.number_words(digits).is_some(),
+            Token::LBrack => self.cartouche_depth += 1,
+            Token::RBrack => self.cartouche_depth = self.cartouche_depth.saturating_sub(1),
+            _ => {}
+        }
+
+        if !matches!(token, Token::Space(..)) {
+            self.prev_is_number = is_number(&token);
+        }
+
+        output
+    }
+
+    fn construct_name(&self, name: &str) -> String {
+        if !self.style.modifiers.spells_names() {
+            return name.to_string();
+        }
+
+        let mut output = String::new();
+        show::construct_name(name, self.style.words, &mut output)
+            .expect("writing to a string never fails");
+        output
+    }
+}
+
+// the numbers of the phrase, when it's only made out of runs of number words separated by
+// spaces, and every run is written the way the number system writes its number
+fn read_numbers(phrase: &str, system: NumberSystem, style: Style) -> Option<Vec<u64>> {
+    let numbers = phrase
+        .split(Spacing::is_space)
+        .filter(|run| !run.is_empty())
+        .map(|run| {
+            let words = run
+                .chars()
+                .map(|c| match Token::from_sitelen(c, style)? {
+                    Token::Word(word) if system.is_number_word(word) => Some(word),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let number = system.parse_words(&words)?;
+            (system.to_words(number)? == words).then_some(number)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+// a part of sitelen UCSUR text, as it's read back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decoded<'a> {
+    Token(Token<'a>),
+
+    // the name that a cartouche spells
+    Name(String),
+
+    // the digits of a number that is written with number words
+    Number(String),
+
+    // a space, or a character that isn't converted
+    Space(&'a str),
+    Text(&'a str),
+}
+
+// reads the sitelen UCSUR input back into tokens, a character at a time
+pub fn decode(input: &str, style: Style) -> Vec<Decoded<'_>> {
+    let mut decoded = Vec::new();
+
+    // number words are only read back as numbers when they make up a whole phrase, which ends
+    // at text that isn't converted, since they're also used as ordinary words (e.g. jan mute)
+    let mut at_phrase_start = true;
+
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if at_phrase_start
+            && !Spacing::is_space(c)
+            && let Some(system) = style.numbers
+        {
+            let end = rest
+                .find(|c| !Spacing::is_space(c) && Token::from_sitelen(c, style).is_none())
+                .unwrap_or(rest.len());
+
+            if let Some(numbers) = read_numbers(&rest[..end], system, style) {
+                decoded.extend(numbers.iter().map(|number| Decoded::Number(number.to_string())));
+                rest = &rest[end..];
+                at_phrase_start = false;
+                continue;
+            }
+        }
+
+        let literal;
+        (literal, rest) = rest.split_at(c.len_utf8());
+
+        // a cartouche that spells a name is read back as the name
+        if c == Modifier::StartOfCartouche.as_sitelen()
+            && let Some((glyphs, leftover)) = rest.split_once(Modifier::EndOfCartouche.as_sitelen())
+            && let Some(name) = read_name(glyphs)
+        {
+            decoded.push(Decoded::Name(name));
+            rest = leftover;
+            at_phrase_start = false;
+            continue;
+        }
+
+        if let Some(token) = Token::from_sitelen(c, style) {
+            decoded.push(Decoded::Token(token));
+            at_phrase_start = false;
+            continue;
+        }
+
+        if Spacing::is_space(c) {
+            decoded.push(Decoded::Space(literal));
+            continue;
+        }
+
+        decoded.push(Decoded::Text(literal));
+        at_phrase_start = true;
+    }
+
+    decoded
+}
+
+pub fn from_sitelen(out: &mut impl io::Write, input: &str, style: Style) -> io::Result<()> {
+    let mut prev_is_token = false;
+    for decoded in decode(input, style) {
+        match &decoded {
+            Decoded::Token(token) => write!(out, "{} ", token.as_literal())?,
+            Decoded::Name(text) | Decoded::Number(text) => write!(out, "{text} ")?,
+
+            // tokens are already followed by a space
+            Decoded::Space(..) if prev_is_token => {}
+            Decoded::Space(..) => write!(out, " ")?,
+            Decoded::Text(text) => write!(out, "{text}")?,
+        }
+
+        prev_is_token = match decoded {
+            Decoded::Space(..) => prev_is_token,
+            Decoded::Text(..) => false,
+            _ => true,
+        };
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::show::ModifierSet;
+    use crate::word_set::WordSet;
+
+    fn sitelen(input: &str, style: Style) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, style, None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn lasina(input: &str, style: Style) -> String {
+        let mut output = Vec::new();
+        from_sitelen(&mut output, input, style).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn pona() -> Style {
+        Style {
+            numbers: Some(NumberSystem::Pona),
+            ..Style::default()
+        }
+    }
+
+    #[test]
+    fn number_words_inside_of_phrases_are_kept() {
+        assert_eq!(lasina(&sitelen("jan mute", Style::default()), pona()), "jan mute ");
+        assert_eq!(lasina(&sitelen("wile ala", Style::default()), pona()), "wile ala ");
+        assert_eq!(lasina(&sitelen("jan 20 li kama", pona()), pona()), "jan mute li kama ");
+    }
+
+    #[test]
+    fn phrases_of_numbers_are_read_back() {
+        assert_eq!(lasina(&sitelen("20 101", pona()), pona()), "20 101 ");
+        assert_eq!(lasina(&sitelen("<1 2>", pona()), pona()), "<1 2 >");
+
+        // the words are only read as a number when they're written the way the number is
+        assert_eq!(lasina(&sitelen("wan wan", Style::default()), pona()), "wan wan ");
+    }
+
+    #[test]
+    fn double_spaces_are_kept_by_default() {
+        assert_eq!(sitelen("jan li  toki", Style::default()), "󱤑󱤧\u{3000}󱥬");
+        assert_eq!(sitelen("jan    toki", Style::default()), "󱤑\u{3000}\u{3000}󱥬");
+    }
+
+    #[test]
+    fn spaces_are_dropped_with_none() {
+        let style = Style {
+            spacing: Spacing::None,
+            ..Style::default()
+        };
+
+        assert_eq!(sitelen("jan li  toki", style), "󱤑󱤧󱥬");
+    }
+
+    #[test]
+    fn spaces_next_to_text_are_kept() {
+        assert_eq!(sitelen("jan <3 li", Style::default()), "󱤑 <3 󱤧");
+    }
+
+    // a font that only has the words and the given characters
+    fn font(chars: &str) -> Style {
+        Style {
+            modifiers: ModifierSet::only(|c| chars.contains(c)),
+            alternatives: WordSet::only([Word::Pona]),
+            ..Style::default()
+        }
+    }
+
+    #[test]
+    fn modifiers_without_glyphs_are_written_in_ascii() {
+        let style = font("");
+        assert_eq!(sitelen("toki-pona+ala", style), "󱥬-󱥔+󱤂");
+        assert_eq!(sitelen("pi(toki ala)", style), "󱥍(󱥬󱤂)");
+        assert_eq!(sitelen("te toki to", style), "\" 󱥬 \"");
+        assert_eq!(sitelen("toki^ pona^", style), "󱥬󱥔\u{fe00}");
+    }
+
+    #[test]
+    fn names_without_cartouches_are_written_in_lasina() {
+        // a name needs the cartouche, the middle dot and the colon
+        for chars in ["", "\u{f1990}\u{f1991}\u{f199c}", "\u{f1990}\u{f1991}\u{f199d}"] {
+            let style = font(chars);
+            assert_eq!(sitelen("jan Sonja li toki", style), "󱤑 Sonja 󱤧󱥬");
+            assert_eq!(sitelen("jan [Sonja]", style), "󱤑 [Sonja]");
+        }
+
+        let style = Style {
+            words: WordSet::Pu,
+            ..font("")
+        };
+        let li_lape = sitelen("li lape", Style::default());
+        assert_eq!(sitelen("kijetesantakalu li lape", style), format!("kijetesantakalu {li_lape}"));
+    }
+
+    #[test]
+    fn numbers_are_only_written_with_the_words_of_the_set() {
+        let style = Style {
+            words: WordSet::only(Word::ALL.iter().copied().filter(|&word| word != Word::Ale)),
+            ..pona()
+        };
+        assert_eq!(sitelen("jan 5 li 100", style), "󱤑󱤭󱤧 100");
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+use crate::lexer::Span;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    // machine readable name of the problem, e.g. `unknown-word`
+    pub code: &'static str,
+    pub span: Span,
+    pub message: String,
+
+    // shown after the position, e.g. a suggestion for fixing the problem
+    pub help: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.span.start)?;
+
+        if let Some(help) = &self.help {
+            write!(f, ", {help}")?;
+        }
+
+        Ok(())
+    }
+}
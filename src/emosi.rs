@@ -0,0 +1,174 @@
+use std::io;
+
+use crate::convert::converted_tokens;
+use crate::lexer::{Location, Spanned, Token};
+use crate::modifier::Modifier;
+use crate::show::{self, Style};
+use crate::spacing::Spacing;
+use crate::word::Word;
+
+const VARIATION_SELECTOR_16: char = '\u{fe0f}';
+
+// writes the sitelen Lasina input in sitelen Emosi. it goes through the same conversion as
+// sitelen UCSUR, and then every glyph is written as its emoji. names are spelled with the emoji
+// of a word for every letter, inside of `[_` `]`, which tells them apart from cartouches of words
+pub fn to_emosi(out: &mut impl io::Write, input: &str, style: Style) -> io::Result<()> {
+    let mut cartouche_depth = 0;
+    for (Spanned { token, .. }, output) in converted_tokens(input, Location::START, style) {
+        match token {
+            Token::LBrack => cartouche_depth += 1,
+            Token::RBrack => cartouche_depth -= usize::from(cartouche_depth > 0),
+            _ => {}
+        }
+
+        match token {
+            Token::Lasina(name) if cartouche_depth > 0 => write!(out, "_{}", spell(name))?,
+            Token::Lasina(name) => write!(out, "[_{}]", spell(name))?,
+            _ => output.chars().try_for_each(|c| write_char(out, c))?,
+        }
+    }
+
+    Ok(())
+}
+
+fn write_char(out: &mut impl io::Write, c: char) -> io::Result<()> {
+    if let Some(word) = Word::from_sitelen(c) {
+        return write!(out, "{}", word.as_emosi());
+    }
+
+    // emoji don't have alternative forms
+    if c == '\u{fe00}' {
+        return Ok(());
+    }
+
+    match Modifier::from_sitelen(c) {
+        Some(Modifier::StartOfCartouche) => write!(out, "["),
+        Some(Modifier::EndOfCartouche) => write!(out, "]"),
+        Some(..) => {
+            let (token, _) = show::TOKEN_MODIFIER
+                .iter()
+                .find(|(_, m)| *m == c)
+                .expect("modifier for token exists");
+            write!(out, "{}", token.as_literal())
+        }
+        None => write!(out, "{c}"),
+    }
+}
+
+fn spell(name: &str) -> String {
+    name.chars()
+        .map(|c| match show::letter_glyph(c).and_then(Word::from_sitelen) {
+            Some(word) => word.as_emosi().to_string(),
+            None => c.to_string(),
+        })
+        .collect()
+}
+
+// the word whose emoji the text starts with, and the rest of the text. the emoji are matched
+// with or without their VARIATION SELECTOR-16
+pub fn read_emoji(text: &str) -> Option<(Word, &str)> {
+    Word::ALL.iter().find_map(|&word| {
+        let mut rest = text;
+        for c in word.as_emosi().chars().filter(|&c| c != VARIATION_SELECTOR_16) {
+            rest = rest.trim_start_matches(VARIATION_SELECTOR_16).strip_prefix(c)?;
+        }
+
+        Some((word, rest.trim_start_matches(VARIATION_SELECTOR_16)))
+    })
+}
+
+// the name that is spelled with the emoji, when there's nothing else in it
+fn read_name(mut emoji: &str) -> Option<String> {
+    let mut name = String::new();
+    while !emoji.is_empty() {
+        let (word, rest) = read_emoji(emoji)?;
+        name += &word.as_lasina()[..1];
+        emoji = rest;
+    }
+
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+// writes the token, separated from the token before it by a space unless either of them is
+// written right next to the other, e.g. a joiner
+fn write_token(
+    out: &mut impl io::Write,
+    prev: Option<Token>,
+    token: Token,
+    literal: &str,
+) -> io::Result<()> {
+    if prev.is_some_and(|prev| !prev.attaches_right() && !token.attaches_left()) {
+        write!(out, " ")?;
+    }
+
+    write!(out, "{literal}")
+}
+
+// writes the sitelen Emosi input in sitelen Lasina
+pub fn from_emosi(out: &mut impl io::Write, input: &str) -> io::Result<()> {
+    // the token that was written last, unless there's text after it
+    let mut prev: Option<Token> = None;
+
+    // whether there are spaces after the last token, which are only written when text comes next
+    let mut spaces = false;
+
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if let Some(leftover) = rest.strip_prefix("[_")
+            && let Some((emoji, leftover)) = leftover.split_once(']')
+            && let Some(name) = read_name(emoji)
+        {
+            write_token(out, prev, Token::Lasina(""), &name)?;
+            rest = leftover;
+            prev = Some(Token::Lasina(""));
+            spaces = false;
+            continue;
+        }
+
+        if let Some((word, leftover)) = read_emoji(rest) {
+            write_token(out, prev, Token::Word(word), word.as_lasina())?;
+            rest = leftover;
+            prev = Some(Token::Word(word));
+            spaces = false;
+            continue;
+        }
+
+        let literal;
+        (literal, rest) = rest.split_at(c.len_utf8());
+
+        // the modifiers are written as they are in sitelen Lasina
+        let token = match c {
+            '「' => Some(Token::Te),
+            '」' => Some(Token::To),
+            _ => show::TOKEN_MODIFIER
+                .iter()
+                .map(|(token, _)| *token)
+                .find(|token| token.as_literal() == literal),
+        };
+
+        if let Some(token) = token.filter(|token| *token != Token::AltSymbol) {
+            write_token(out, prev, token, token.as_literal())?;
+            prev = Some(token);
+            spaces = false;
+            continue;
+        }
+
+        if Spacing::is_space(c) && prev.is_some() {
+            spaces = true;
+            continue;
+        }
+
+        // the spaces at the end of a line are dropped
+        if spaces && !matches!(c, '\r' | '\n') {
+            write!(out, " ")?;
+        }
+
+        write!(out, "{c}")?;
+        prev = None;
+        spaces = false;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,392 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use crate::word::Word;
+
+// the characters and the variation sequences that a TrueType or OpenType font has glyphs for,
+// read from its `cmap` table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Font {
+    // sorted, and without overlapping or adjacent ranges
+    chars: Vec<RangeInclusive<u32>>,
+    variations: Vec<Variation>,
+}
+
+// the sequences of a variation selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Variation {
+    selector: u32,
+
+    // the sequences that are shown with the same glyph as the base character alone
+    default: Vec<RangeInclusive<u32>>,
+
+    // the sequences that have a glyph of their own
+    non_default: Vec<u32>,
+}
+
+impl Font {
+    // returns `None` when the data isn't a font, or it has no `cmap` subtable for Unicode. only
+    // the first font of a collection is read
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut font = data;
+        if data.get(..4)? == b"ttcf" {
+            font = data.get(read_u32(data, 12)? as usize..)?;
+        }
+
+        let tables = read_u16(font, 4)? as usize;
+        let cmap = (0..tables).find_map(|i| {
+            let record = font.get(12 + i * 16..12 + (i + 1) * 16)?;
+            (&record[..4] == b"cmap").then(|| read_u32(record, 8))?
+        })?;
+
+        // table offsets are from the start of the file, even in collections
+        let cmap = data.get(cmap as usize..)?;
+
+        let mut output = Font::default();
+        let mut found = false;
+        for i in 0..read_u16(cmap, 2)? as usize {
+            let platform = read_u16(cmap, 4 + i * 8)?;
+            let encoding = read_u16(cmap, 6 + i * 8)?;
+            let subtable = cmap.get(read_u32(cmap, 8 + i * 8)? as usize..)?;
+
+            let unicode = matches!((platform, encoding), (0, _) | (3, 1) | (3, 10));
+            match read_u16(subtable, 0)? {
+                4 if unicode => output.chars.extend(parse_format_4(subtable)?),
+                12 if unicode => output.chars.extend(parse_format_12(subtable)?),
+                14 if platform == 0 && encoding == 5 => {
+                    output.variations = parse_format_14(subtable)?;
+                }
+                _ => continue,
+            }
+
+            found = true;
+        }
+
+        output.chars = merge(output.chars);
+        found.then_some(output)
+    }
+
+    pub fn supports(&self, c: char) -> bool {
+        let c = c as u32;
+        self.chars
+            .binary_search_by(|range| {
+                if *range.end() < c {
+                    Ordering::Less
+                } else if *range.start() > c {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    // whether the character followed by the variation selector has a glyph
+    pub fn supports_variation(&self, c: char, selector: char) -> bool {
+        let Some(variation) = self.variations.iter().find(|v| v.selector == selector as u32) else {
+            return false;
+        };
+
+        variation.non_default.contains(&(c as u32))
+            || (self.supports(c)
+                && variation.default.iter().any(|range| range.contains(&(c as u32))))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = Word> + '_ {
+        Word::ALL.iter().copied().filter(|word| self.supports(word.as_sitelen()))
+    }
+}
+
+// sorts the ranges, and joins the ones that overlap or are next to each other
+fn merge(mut ranges: Vec<RangeInclusive<u32>>) -> Vec<RangeInclusive<u32>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_by_key(|range| *range.start());
+
+    let mut merged: Vec<RangeInclusive<u32>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start().saturating_sub(1) <= *last.end() => {
+                *last = *last.start()..=*range.end().max(last.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+// adds the character to the ranges, extending the last of them when it comes right after it
+fn push(chars: &mut Vec<RangeInclusive<u32>>, c: u32) {
+    match chars.last_mut() {
+        Some(last) if last.end().checked_add(1) == Some(c) => *last = *last.start()..=c,
+        _ => chars.push(c..=c),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u24(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// segment mapping to delta values, for the Basic Multilingual Plane
+fn parse_format_4(subtable: &[u8]) -> Option<Vec<RangeInclusive<u32>>> {
+    let segments = read_u16(subtable, 6)? as usize / 2;
+    let end_codes = 14;
+    let start_codes = end_codes + segments * 2 + 2;
+    let deltas = start_codes + segments * 2;
+    let range_offsets = deltas + segments * 2;
+
+    let mut chars = Vec::new();
+    for i in 0..segments {
+        let end = read_u16(subtable, end_codes + i * 2)?;
+        let start = read_u16(subtable, start_codes + i * 2)?;
+        let delta = read_u16(subtable, deltas + i * 2)?;
+        let range_offset = read_u16(subtable, range_offsets + i * 2)? as usize;
+
+        // the last segment only maps 0xFFFF to the missing glyph
+        if start == 0xffff {
+            continue;
+        }
+
+        if range_offset == 0 {
+            (start..=end)
+                .filter(|c| c.wrapping_add(delta) != 0)
+                .for_each(|c| push(&mut chars, c as u32));
+            continue;
+        }
+
+        // the offset is from where it's read, into the glyph id array
+        for c in start..=end {
+            let offset = range_offsets + i * 2 + range_offset + (c - start) as usize * 2;
+            if read_u16(subtable, offset)? != 0 {
+                push(&mut chars, c as u32);
+            }
+        }
+    }
+
+    Some(chars)
+}
+
+// segmented coverage, for all of Unicode
+fn parse_format_12(subtable: &[u8]) -> Option<Vec<RangeInclusive<u32>>> {
+    let groups = read_u32(subtable, 12)? as usize;
+    (0..groups)
+        .map(|i| {
+            let start = read_u32(subtable, 16 + i * 12)?;
+            let end = read_u32(subtable, 20 + i * 12)?;
+            let glyph = read_u32(subtable, 24 + i * 12)?;
+
+            // a group that starts at the missing glyph doesn't map its first character
+            Some(if glyph == 0 { start.checked_add(1)?..=end } else { start..=end })
+        })
+        .collect()
+}
+
+// Unicode variation sequences
+fn parse_format_14(subtable: &[u8]) -> Option<Vec<Variation>> {
+    let records = read_u32(subtable, 6)? as usize;
+    (0..records)
+        .map(|i| {
+            let record = 10 + i * 11;
+            let selector = read_u24(subtable, record)?;
+            let default_offset = read_u32(subtable, record + 3)? as usize;
+            let non_default_offset = read_u32(subtable, record + 7)? as usize;
+
+            let mut default = Vec::new();
+            if default_offset != 0 {
+                let table = subtable.get(default_offset..)?;
+                for j in 0..read_u32(table, 0)? as usize {
+                    let start = read_u24(table, 4 + j * 4)?;
+                    let count = *table.get(7 + j * 4)? as u32;
+                    default.push(start..=start + count);
+                }
+            }
+
+            let mut non_default = Vec::new();
+            if non_default_offset != 0 {
+                let table = subtable.get(non_default_offset..)?;
+                for j in 0..read_u32(table, 0)? as usize {
+                    non_default.push(read_u24(table, 4 + j * 5)?);
+                }
+            }
+
+            Some(Variation {
+                selector,
+                default,
+                non_default,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16s(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|value| value.to_be_bytes()).collect()
+    }
+
+    fn u32s(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|value| value.to_be_bytes()).collect()
+    }
+
+    fn u24(value: u32) -> [u8; 3] {
+        let [_, bytes @ ..] = value.to_be_bytes();
+        bytes
+    }
+
+    // a font with only a `cmap` table, that has the given subtables with their platform and
+    // encoding
+    fn font(subtables: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut cmap = u16s(&[0, subtables.len() as u16]);
+        let mut offset = 4 + subtables.len() * 8;
+        for (platform, encoding, subtable) in subtables {
+            cmap.extend(u16s(&[*platform, *encoding]));
+            cmap.extend(u32s(&[offset as u32]));
+            offset += subtable.len();
+        }
+
+        for (_, _, subtable) in subtables {
+            cmap.extend(subtable);
+        }
+
+        let mut data = u32s(&[0x0001_0000]);
+        data.extend(u16s(&[1, 16, 0, 0]));
+        data.extend(b"cmap");
+        data.extend(u32s(&[0, 28, cmap.len() as u32]));
+        data.extend(cmap);
+        data
+    }
+
+    // A to C by a delta, U+3000 by the glyph id array (U+3001 maps to the missing glyph there),
+    // and the last segment
+    fn format_4() -> Vec<u8> {
+        let mut subtable = u16s(&[4, 0, 0, 6, 4, 1, 2]);
+        subtable.extend(u16s(&[0x43, 0x3001, 0xffff, 0]));
+        subtable.extend(u16s(&[0x41, 0x3000, 0xffff]));
+        subtable.extend(u16s(&[0x1000, 0, 1]));
+        subtable.extend(u16s(&[0, 4, 0]));
+        subtable.extend(u16s(&[5, 0]));
+        subtable
+    }
+
+    // U+F1900 to U+F1902, and U+F1903 to U+F1904 from the missing glyph
+    fn format_12() -> Vec<u8> {
+        let mut subtable = u16s(&[12, 0]);
+        subtable.extend(u32s(&[0, 0, 2]));
+        subtable.extend(u32s(&[0xf1900, 0xf1902, 1]));
+        subtable.extend(u32s(&[0xf1903, 0xf1904, 0]));
+        subtable
+    }
+
+    // U+FE00 after U+F1900 and U+F1901 by default, and after U+F1950 with a glyph of its own
+    fn format_14() -> Vec<u8> {
+        let mut subtable = u16s(&[14]);
+        subtable.extend(u32s(&[0, 1]));
+        subtable.extend(u24(0xfe00));
+        subtable.extend(u32s(&[21, 29]));
+        subtable.extend(u32s(&[1]));
+        subtable.extend(u24(0xf1900));
+        subtable.push(1);
+        subtable.extend(u32s(&[1]));
+        subtable.extend(u24(0xf1950));
+        subtable.extend(u16s(&[7]));
+        subtable
+    }
+
+    #[test]
+    fn segment_mapping() {
+        let font = Font::parse(&font(&[(3, 1, format_4())])).unwrap();
+        assert!(['A', 'B', 'C', '\u{3000}'].into_iter().all(|c| font.supports(c)));
+        assert!(!['@', 'D', '\u{3001}', '\u{ffff}'].into_iter().any(|c| font.supports(c)));
+
+        // the characters of a segment are a single range
+        assert_eq!(font.chars, [0x41..=0x43, 0x3000..=0x3000]);
+    }
+
+    #[test]
+    fn segmented_coverage() {
+        let font = Font::parse(&font(&[(3, 10, format_12())])).unwrap();
+        assert!(font.supports('\u{f1900}') && font.supports('\u{f1902}') && font.supports('\u{f1904}'));
+        assert!(!font.supports('\u{f1903}') && !font.supports('\u{f1905}'));
+        assert_eq!(font.words().collect::<Vec<_>>(), [Word::A, Word::Akesi, Word::Ala, Word::Ale]);
+    }
+
+    #[test]
+    fn ranges_of_subtables_are_merged() {
+        let mut overlapping = u16s(&[12, 0]);
+        overlapping.extend(u32s(&[0, 0, 2]));
+        overlapping.extend(u32s(&[0x44, 0x50, 1]));
+        overlapping.extend(u32s(&[0x2fff, 0x2fff, 1]));
+
+        let font = Font::parse(&font(&[(3, 1, format_4()), (0, 4, overlapping)])).unwrap();
+        assert_eq!(font.chars, [0x41..=0x50, 0x2fff..=0x3000]);
+    }
+
+    #[test]
+    fn variation_sequences() {
+        let font = Font::parse(&font(&[(3, 10, format_12()), (0, 5, format_14())])).unwrap();
+        assert!(font.supports_variation('\u{f1900}', '\u{fe00}'));
+        assert!(font.supports_variation('\u{f1901}', '\u{fe00}'));
+        assert!(font.supports_variation('\u{f1950}', '\u{fe00}'));
+        assert!(!font.supports_variation('\u{f1902}', '\u{fe00}'));
+        assert!(!font.supports_variation('\u{f1900}', '\u{fe01}'));
+    }
+
+    #[test]
+    fn truncated_fonts() {
+        let data = font(&[(3, 1, format_4()), (3, 10, format_12()), (0, 5, format_14())]);
+        assert!(Font::parse(&data).is_some());
+
+        // the glyph id at the end of the last variation sequence is never read
+        for len in 0..data.len() - 2 {
+            assert_eq!(Font::parse(&data[..len]), None, "{len} bytes");
+        }
+    }
+
+    #[test]
+    fn malformed_tables() {
+        // no subtable for Unicode
+        assert_eq!(Font::parse(&font(&[(1, 0, format_4())])), None);
+        assert_eq!(Font::parse(&font(&[])), None);
+
+        // more segments and groups than there are
+        let mut segments = format_4();
+        segments[6..8].copy_from_slice(&u16s(&[0xfffe]));
+        assert_eq!(Font::parse(&font(&[(3, 1, segments)])), None);
+
+        let mut groups = format_12();
+        groups[12..16].copy_from_slice(&u32s(&[u32::MAX]));
+        assert_eq!(Font::parse(&font(&[(3, 10, groups)])), None);
+
+        // a group from the missing glyph that starts at the last value
+        let mut last = u16s(&[12, 0]);
+        last.extend(u32s(&[0, 0, 1, u32::MAX, u32::MAX, 0]));
+        assert_eq!(Font::parse(&font(&[(3, 10, last)])), None);
+
+        // a glyph id array that ends early, and a table offset past the end
+        let mut glyphs = format_4();
+        glyphs.truncate(glyphs.len() - 2);
+        assert_eq!(Font::parse(&font(&[(3, 1, glyphs)])), None);
+
+        let mut variations = format_14();
+        variations[13..17].copy_from_slice(&u32s(&[0xffff]));
+        assert_eq!(Font::parse(&font(&[(3, 10, format_12()), (0, 5, variations)])), None);
+
+        // a collection whose font is past the end
+        let mut collection = b"ttcf".to_vec();
+        collection.extend(u32s(&[0x0001_0000, 1, 0xffff]));
+        assert_eq!(Font::parse(&collection), None);
+    }
+}
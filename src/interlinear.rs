@@ -0,0 +1,257 @@
+use std::io;
+
+use crate::cluster::clusters;
+use crate::convert::converted_tokens;
+use crate::lexer::{Location, Token};
+use crate::show::Style;
+use crate::spacing::Spacing;
+use crate::width::width;
+
+// a piece of the line that is shown as a unit, with its sitelen Lasina above its glyphs
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cell {
+    pub lasina: String,
+    pub sitelen: String,
+
+    // the short glosses of the words of the cell, joined with dashes
+    pub gloss: String,
+}
+
+// splits the line into cells. a cell is made out of the tokens whose output is a single
+// cluster, so joined glyphs, long glyphs and cartouches are never torn apart, and the brackets
+// around names are left out, so the name is shown above its cartouche. text that isn't
+// converted stays together as it was typed
+pub fn cells(line: &str, style: Style) -> Vec<Cell> {
+    let tokens: Vec<_> = converted_tokens(line, Location::START, style)
+        .filter(|(spanned, _)| !matches!(spanned.token, Token::Newline(..)))
+        .collect();
+
+    let output: String = tokens.iter().map(|(_, output)| output.as_str()).collect();
+    let boundaries: Vec<usize> = clusters(&output).map(|cluster| cluster.offset).collect();
+
+    let mut cells = Vec::new();
+    let mut cell = Cell::default();
+
+    let mut offset = 0;
+    let mut prev_is_text = false;
+    for (spanned, text) in &tokens {
+        let is_text = !matches!(spanned.token, Token::Space(..)) && text == spanned.token.as_literal();
+        if boundaries.contains(&offset) && !(prev_is_text && is_text) {
+            cells.push(std::mem::take(&mut cell));
+        }
+
+        if let Token::Word(word) = spanned.token {
+            if !cell.gloss.is_empty() {
+                cell.gloss.push('-');
+            }
+
+            cell.gloss += word.short_gloss();
+        }
+
+        match spanned.token {
+            Token::Space(..) => cell.lasina.push(' '),
+            Token::LBrack | Token::RBrack => {}
+            token => cell.lasina += token.as_literal(),
+        }
+
+        cell.sitelen += text;
+        offset += text.len();
+        prev_is_text = is_text;
+    }
+
+    cells.push(cell);
+
+    for cell in &mut cells {
+        cell.lasina = cell.lasina.trim().to_string();
+        cell.sitelen = cell.sitelen.trim_matches(Spacing::is_space).to_string();
+    }
+
+    cells.retain(|cell| !cell.lasina.is_empty() || !cell.sitelen.is_empty());
+    cells
+}
+
+// writes the line twice, the sitelen Lasina words above their glyphs, with the cells padded so
+// they line up. with the gloss, the meanings of the words are written under the glyphs
+pub fn write_text(out: &mut impl io::Write, line: &str, style: Style, gloss: bool) -> io::Result<()> {
+    let cells = cells(line, style);
+    if gloss {
+        write_rows(out, &cells, &[|cell| &cell.lasina, |cell| &cell.sitelen, |cell| &cell.gloss])
+    } else {
+        write_rows(out, &cells, &[|cell| &cell.lasina, |cell| &cell.sitelen])
+    }
+}
+
+// writes the glyphs of the line with the meanings of the words under them
+pub fn write_glossed(out: &mut impl io::Write, line: &str, style: Style) -> io::Result<()> {
+    write_rows(out, &cells(line, style), &[|cell| &cell.sitelen, |cell| &cell.gloss])
+}
+
+fn write_rows(
+    out: &mut impl io::Write,
+    cells: &[Cell],
+    rows: &[fn(&Cell) -> &String],
+) -> io::Result<()> {
+    if cells.is_empty() {
+        return writeln!(out);
+    }
+
+    for row in rows {
+        let mut line = String::new();
+        for cell in cells {
+            let cell_width = rows.iter().map(|row| width(row(cell))).max().unwrap_or_default();
+            pad(&mut line, row(cell), cell_width);
+        }
+
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn pad(row: &mut String, text: &str, cell_width: usize) {
+    *row += text;
+    row.extend(std::iter::repeat_n(' ', cell_width - width(text) + 1));
+}
+
+// writes the line as HTML, with the sitelen Lasina of every cell as a ruby annotation of its
+// glyphs. text that isn't converted is written without an annotation
+pub fn write_html(out: &mut impl io::Write, line: &str, style: Style) -> io::Result<()> {
+    for (i, cell) in cells(line, style).iter().enumerate() {
+        if i > 0 {
+            write!(out, " ")?;
+        }
+
+        if cell.lasina == cell.sitelen {
+            write_escaped(out, &cell.sitelen)?;
+            continue;
+        }
+
+        write!(out, "<ruby>")?;
+        write_escaped(out, &cell.sitelen)?;
+        write!(out, "<rt>")?;
+        write_escaped(out, &cell.lasina)?;
+        write!(out, "</rt></ruby>")?;
+    }
+
+    writeln!(out, "<br>")
+}
+
+fn write_escaped(out: &mut impl io::Write, text: &str) -> io::Result<()> {
+    for c in text.chars() {
+        match c {
+            '&' => write!(out, "&amp;")?,
+            '<' => write!(out, "&lt;")?,
+            '>' => write!(out, "&gt;")?,
+            '"' => write!(out, "&quot;")?,
+            c => write!(out, "{c}")?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+
+    fn sitelen(input: &str) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, Style::default(), None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn text(line: &str) -> String {
+        let mut output = Vec::new();
+        write_text(&mut output, line, Style::default(), false).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn html(line: &str) -> String {
+        let mut output = Vec::new();
+        write_html(&mut output, line, Style::default()).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn clusters_are_cells() {
+        let cells = cells("jan [Sonja] li toki-pona pi(toki ala)", Style::default());
+        let lasina: Vec<_> = cells.iter().map(|cell| cell.lasina.as_str()).collect();
+        let glyphs: Vec<_> = cells.iter().map(|cell| cell.sitelen.clone()).collect();
+
+        // the name is shown above its cartouche, without the brackets
+        assert_eq!(lasina, ["jan", "Sonja", "li", "toki-pona", "pi(toki ala)"]);
+        assert_eq!(glyphs, ["jan", "[Sonja]", "li", "toki-pona", "pi(toki ala)"].map(sitelen));
+    }
+
+    #[test]
+    fn cells_line_up() {
+        let [jan, name, li, toki_pona] = ["jan", "[Sonja]", "li", "toki-pona"].map(sitelen);
+        assert_eq!(
+            text("jan [Sonja] li toki-pona"),
+            format!("jan Sonja li toki-pona\n{jan}  {name}  {li} {toki_pona}\n"),
+        );
+
+        // a glyph takes two columns, so a wide word pads the glyph under it
+        let [raccoon, lon] = ["kijetesantakalu", "lon"].map(sitelen);
+        assert_eq!(
+            text("kijetesantakalu li lon"),
+            format!("kijetesantakalu li lon\n{raccoon}{} {li} {lon}\n", " ".repeat(15 - 2)),
+        );
+
+        // the long glyph is as wide as the glyphs inside of it
+        let long_pi = sitelen("pi(toki ala)");
+        assert_eq!(text("pi(toki ala) li"), format!("pi(toki ala) li\n{long_pi}{} {li}\n", " ".repeat(12 - 6)));
+        assert_eq!(text(""), "\n");
+    }
+
+    fn glossed(line: &str) -> String {
+        let mut output = Vec::new();
+        write_glossed(&mut output, line, Style::default()).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn first_meaning_under_every_glyph() {
+        let [mi, moku, lili] = ["mi", "moku", "lili"].map(sitelen);
+        assert_eq!(glossed("mi moku lili"), format!("{mi} {moku}  {lili}\nI  eat small\n"));
+
+        // a gloss that is wider than its glyph pads the glyph, and joined words share a cell
+        let [li, toki_pona] = ["li", "toki-pona"].map(sitelen);
+        assert_eq!(
+            glossed("li toki-pona"),
+            format!("{li}{} {toki_pona}\npredicate marker language-good\n", " ".repeat(16 - 2)),
+        );
+    }
+
+    #[test]
+    fn tokens_without_a_gloss() {
+        let glosses = |line| -> Vec<(String, String)> {
+            cells(line, Style::default()).into_iter().map(|cell| (cell.lasina, cell.gloss)).collect()
+        };
+
+        let expected = [("jan", "person"), ("Sonja", ""), ("li", "predicate marker"), ("lon", "at"), (",", ""), ("5!", "")];
+        assert_eq!(glosses("jan [Sonja] li lon, 5!"), expected.map(|(lasina, gloss)| (lasina.to_string(), gloss.to_string())));
+
+        // the name, the number and the punctuation still take their columns
+        let mut output = Vec::new();
+        write_text(&mut output, "jan [Sonja] li lon 5", Style::default(), true).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().nth(2), Some("person       predicate marker at"));
+    }
+
+    #[test]
+    fn html_ruby() {
+        let [jan, name, li, a] = ["jan", "[Sonja]", "li", "a"].map(sitelen);
+        assert_eq!(
+            html("jan [Sonja] li <3 & \"a\"\n"),
+            format!(
+                "<ruby>{jan}<rt>jan</rt></ruby> <ruby>{name}<rt>Sonja</rt></ruby> <ruby>{li}<rt>li</rt></ruby> \
+                 &lt;3 &amp; &quot; <ruby>{a}<rt>a</rt></ruby> &quot;<br>\n"
+            ),
+        );
+
+        // every line ends in a line break
+        assert_eq!(html("a\n") + &html("\n"), format!("<ruby>{a}<rt>a</rt></ruby><br>\n<br>\n"));
+    }
+}
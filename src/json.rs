@@ -0,0 +1,117 @@
+use std::io;
+
+use crate::lexer::{Location, Span, Spanned, Token};
+use crate::modifier::Modifier;
+use crate::show;
+
+// writes the text as a quoted JSON string
+pub fn write_str(out: &mut impl io::Write, text: &str) -> io::Result<()> {
+    write!(out, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    write!(out, "\"")
+}
+
+pub fn write_location(out: &mut impl io::Write, location: Location) -> io::Result<()> {
+    let Location { offset, line, column } = location;
+    write!(out, r#"{{"offset":{offset},"line":{line},"column":{column}}}"#)
+}
+
+pub fn write_span(out: &mut impl io::Write, span: Span) -> io::Result<()> {
+    write!(out, r#"{{"start":"#)?;
+    write_location(out, span.start)?;
+    write!(out, r#","end":"#)?;
+    write_location(out, span.end)?;
+    write!(out, "}}")
+}
+
+// a token of the input together with its sitelen UCSUR output
+pub fn write_token(out: &mut impl io::Write, spanned: &Spanned, output: &str) -> io::Result<()> {
+    let Spanned { token, span } = spanned;
+
+    write!(out, r#"{{"kind":"{}","literal":"#, token.kind())?;
+    write_str(out, token.as_literal())?;
+    write!(out, r#","span":"#)?;
+    write_span(out, *span)?;
+    write!(out, r#","output":"#)?;
+    write_str(out, output)?;
+
+    if let Token::Word(word) = token {
+        write!(out, r#","codepoint":{}"#, u32::from(word.as_sitelen()))?;
+    }
+
+    if let Some(modifier) = modifier_name(*token) {
+        write!(out, r#","modifier":"{modifier}""#)?;
+    }
+
+    write!(out, "}}")
+}
+
+// the name of the modifier that the token stands for, no matter how it's written in the output,
+// e.g. a sentence full stop isn't a middle dot even when it's written as one
+fn modifier_name(token: Token) -> Option<String> {
+    match token {
+        Token::Te => Some("StartOfQuote".to_string()),
+        Token::To => Some("EndOfQuote".to_string()),
+        Token::AltSymbol => Some("AltSymbol".to_string()),
+        _ => {
+            let (_, c) = show::TOKEN_MODIFIER.iter().find(|(t, _)| *t == token)?;
+            Modifier::from_sitelen(*c).map(|modifier| format!("{modifier:?}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::converted_tokens;
+    use crate::show::Style;
+
+    fn tokens(input: &str) -> Vec<String> {
+        converted_tokens(input, Location::START, Style::default())
+            .map(|(spanned, output)| {
+                let mut json = Vec::new();
+                write_token(&mut json, &spanned, &output).unwrap();
+                String::from_utf8(json).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn modifiers_are_named_after_the_token() {
+        let tokens = tokens("toki-pona. te a^ to");
+        assert!(tokens[1].contains(r#""modifier":"StackingJoiner""#));
+
+        // the full stop is written as a middle dot, but it isn't one
+        assert!(tokens[3].starts_with(r#"{"kind":"Punct""#));
+        assert!(!tokens[3].contains("modifier"));
+
+        assert!(tokens[5].contains(r#""modifier":"StartOfQuote""#));
+        assert!(tokens[8].contains(r#""modifier":"AltSymbol""#));
+        assert!(tokens[10].contains(r#""modifier":"EndOfQuote""#));
+    }
+
+    #[test]
+    fn words_have_their_codepoint() {
+        assert_eq!(
+            tokens("toki")[0],
+            r#"{"kind":"Word","literal":"toki","span":{"start":{"offset":0,"line":1,"column":1},"end":{"offset":4,"line":1,"column":5}},"output":"󱥬","codepoint":989548}"#
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        let mut json = Vec::new();
+        write_str(&mut json, "\"a\\\n\u{1}").unwrap();
+        assert_eq!(String::from_utf8(json).unwrap(), r#""\"a\\\n\u0001""#);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::cluster::{Cluster, clusters};
+use crate::spacing::Spacing;
+use crate::width::cluster_width;
+
+// the order that the columns of a vertical layout are read in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    RightToLeft,
+    LeftToRight,
+}
+
+impl std::str::FromStr for Direction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "rtl" => Ok(Self::RightToLeft),
+            "ltr" => Ok(Self::LeftToRight),
+            _ => Err(()),
+        }
+    }
+}
+
+// arranges the clusters of the sitelen UCSUR text into columns, read from top to bottom, that
+// are at most the given number of clusters high. a line break in the text starts a new column,
+// spaces are dropped since every cluster gets a cell of its own, and the cells are padded with
+// U+3000 IDEOGRAPHIC SPACE so the columns line up
+pub fn vertical(text: &str, height: usize, direction: Direction) -> String {
+    let height = height.max(1);
+
+    let mut columns: Vec<Vec<Cluster>> = vec![Vec::new()];
+    for cluster in clusters(text) {
+        let column = columns.last_mut().expect("there's always a column");
+        if cluster.text.ends_with('\n') {
+            if !column.is_empty() {
+                columns.push(Vec::new());
+            }
+        } else if !cluster.text.chars().all(Spacing::is_space) {
+            if column.len() == height {
+                columns.push(vec![cluster]);
+            } else {
+                column.push(cluster);
+            }
+        }
+    }
+
+    if columns.last().is_some_and(Vec::is_empty) {
+        columns.pop();
+    }
+
+    if direction == Direction::RightToLeft {
+        columns.reverse();
+    }
+
+    // a column is as wide as its widest cell, which is at least a full-width cell
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|column| column.iter().map(cluster_width).max().unwrap_or_default().max(2))
+        .collect();
+
+    let rows = columns.iter().map(Vec::len).max().unwrap_or_default();
+    let mut output = String::new();
+    for row in 0..rows {
+        let mut line = String::new();
+        for (i, (column, width)) in columns.iter().zip(&widths).enumerate() {
+            if i > 0 {
+                line.push('\u{3000}');
+            }
+
+            let (cell, cell_width) = column
+                .get(row)
+                .map_or(("", 0), |cluster| (cluster.text, cluster_width(cluster)));
+
+            line += cell;
+            pad(&mut line, width - cell_width);
+        }
+
+        output += line.trim_end_matches(Spacing::is_space);
+        output.push('\n');
+    }
+
+    output
+}
+
+// fills the given number of columns, with half-width spaces only when the number is odd
+fn pad(line: &mut String, columns: usize) {
+    line.extend(std::iter::repeat_n('\u{3000}', columns / 2));
+    if columns % 2 == 1 {
+        line.push(' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::show::Style;
+
+    fn sitelen(input: &str) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, Style::default(), None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn columns_are_read_from_the_right_by_default() {
+        let text = sitelen("mi toki pona");
+        let [mi, toki, pona] = ["mi", "toki", "pona"].map(sitelen);
+        assert_eq!(vertical(&text, 2, Direction::default()), format!("{pona}\u{3000}{mi}\n\u{3000}\u{3000}{toki}\n"));
+        assert_eq!(vertical(&text, 2, Direction::LeftToRight), format!("{mi}\u{3000}{pona}\n{toki}\n"));
+    }
+
+    #[test]
+    fn line_breaks_start_a_new_column() {
+        let text = sitelen("mi\ntoki\n\npona");
+        let [mi, toki, pona] = ["mi", "toki", "pona"].map(sitelen);
+        assert_eq!(vertical(&text, 8, Direction::LeftToRight), format!("{mi}\u{3000}{toki}\u{3000}{pona}\n"));
+    }
+
+    #[test]
+    fn wide_cells_pad_their_column() {
+        let text = sitelen("toki-pona ala");
+        let cell = sitelen("toki-pona");
+        let ala = sitelen("ala");
+        assert_eq!(vertical(&text, 2, Direction::LeftToRight), format!("{cell}\n{ala}\n"));
+
+        // a cartouche is as wide as its glyphs
+        let text = sitelen("[Sonja] a");
+        let name = sitelen("[Sonja]");
+        let a = sitelen("a");
+        let output = vertical(&text, 1, Direction::LeftToRight);
+        assert_eq!(output, format!("{name}\u{3000}{a}\n"));
+    }
+
+    #[test]
+    fn empty_text_has_no_rows() {
+        assert_eq!(vertical("", 4, Direction::default()), "");
+        assert_eq!(vertical("\n\n", 0, Direction::default()), "");
+        assert_eq!("rtl".parse(), Ok(Direction::RightToLeft));
+        assert_eq!("up".parse::<Direction>(), Err(()));
+    }
+}
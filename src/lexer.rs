@@ -1,10 +1,17 @@
 use std::str::FromStr as _;
 
-use crate::show;
+use crate::ligature::{LigatureFont, Names};
+use crate::punctuation::Punct;
+use crate::show::{self, Style};
 use crate::word::Word;
 
 const ALPHABET: &str = "aeijklmnopstuw";
 
+// whether the character is a letter of toki pona, in either case
+fn is_letter(c: char) -> bool {
+    ALPHABET.contains(c.to_ascii_lowercase())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Token<'a> {
     // ()
@@ -34,6 +41,12 @@ pub enum Token<'a> {
     // :
     Colon,
 
+    // sentence punctuation
+    Punct(Punct),
+
+    // consecutive ASCII digits
+    Number(&'a str),
+
     // start of quotes
     Te,
 
@@ -49,22 +62,84 @@ pub enum Token<'a> {
     // non sitelen Lasina word but uses all alphabetical letters
     Lasina(&'a str),
 
-    // consecutive spaces
+    // consecutive spaces and tabs
     Space(&'a str),
 
+    // consecutive line breaks
+    Newline(&'a str),
+
     // everything else
     Other(&'a str),
 }
 
 impl<'a> Token<'a> {
-    pub fn from_sitelen(c: char) -> Option<Self> {
-        show::TOKEN_MODIFIER
-            .iter()
-            .find(|(_, m)| *m == c)
-            .map(|(t, _)| *t)
+    pub fn from_sitelen(c: char, style: Style) -> Option<Self> {
+        style
+            .punctuation
+            .decode(c)
+            .map(Self::Punct)
+            .or_else(|| {
+                show::TOKEN_MODIFIER
+                    .iter()
+                    .find(|(_, m)| *m == c)
+                    .map(|(t, _)| *t)
+            })
             .or_else(|| Word::from_sitelen(c).map(Self::Word))
     }
 
+    // whether the token is written right after the one before it in sitelen Lasina, without a
+    // space in between
+    pub const fn attaches_left(&self) -> bool {
+        matches!(
+            self,
+            Token::RParen
+                | Token::RBrack
+                | Token::RBrace
+                | Token::Plus
+                | Token::Minus
+                | Token::Underscore
+                | Token::Dot
+                | Token::Colon
+                | Token::AltSymbol
+                | Token::Punct(..)
+        )
+    }
+
+    // whether the token is written right before the one after it, without a space in between
+    pub const fn attaches_right(&self) -> bool {
+        matches!(
+            self,
+            Token::LParen | Token::LBrack | Token::LBrace | Token::Plus | Token::Minus | Token::Underscore
+        )
+    }
+
+    // the name of the variant
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Token::LParen => "LParen",
+            Token::RParen => "RParen",
+            Token::LBrack => "LBrack",
+            Token::RBrack => "RBrack",
+            Token::LBrace => "LBrace",
+            Token::RBrace => "RBrace",
+            Token::Plus => "Plus",
+            Token::Minus => "Minus",
+            Token::Underscore => "Underscore",
+            Token::Dot => "Dot",
+            Token::Colon => "Colon",
+            Token::Punct(..) => "Punct",
+            Token::Number(..) => "Number",
+            Token::Te => "Te",
+            Token::To => "To",
+            Token::AltSymbol => "AltSymbol",
+            Token::Word(..) => "Word",
+            Token::Lasina(..) => "Lasina",
+            Token::Space(..) => "Space",
+            Token::Newline(..) => "Newline",
+            Token::Other(..) => "Other",
+        }
+    }
+
     pub fn as_literal(&self) -> &'a str {
         match self {
             Token::LParen => "(",
@@ -78,25 +153,90 @@ impl<'a> Token<'a> {
             Token::Underscore => "_",
             Token::Dot => ".",
             Token::Colon => ":",
+            Token::Punct(punct) => punct.as_ascii(),
             Token::Te => "te",
             Token::To => "to",
             Token::AltSymbol => "^",
             Token::Word(word) => word.as_lasina(),
             Token::Space(spaces) => spaces,
+            Token::Newline(newlines) => newlines,
+            Token::Number(digits) => digits,
             Token::Lasina(word) => word,
             Token::Other(other) => other,
         }
     }
 }
 
+// what the lexer remembers from the tokens before the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct State {
+    // quotes that are the same on both sides are told apart by whether a quote is already open
+    pub in_quote: bool,
+
+    // whether the next token is the name after the `[_` of a font that spells names by itself
+    spelled_name: bool,
+}
+
 // token is either a "sitelen Lasina" or something else
 pub fn next_token(input: &'_ str) -> (Token<'_>, &'_ str) {
+    next_token_in(input, None, &mut State::default())
+}
+
+// same as `next_token`, but also accepts the conventions of the ligature font, which depend on
+// the tokens before it
+pub fn next_token_in<'a>(
+    input: &'a str,
+    dialect: Option<LigatureFont>,
+    state: &mut State,
+) -> (Token<'a>, &'a str) {
+    let (token, leftover) = lex(input, dialect, *state);
+
+    state.in_quote = match token {
+        Token::Te => true,
+        Token::To => false,
+        _ => state.in_quote,
+    };
+
+    // only the `[_` of a spelled name is a cartouche that's longer than a single character
+    state.spelled_name = token == Token::LBrack && input.len() - leftover.len() == 2;
+
+    (token, leftover)
+}
+
+fn lex(input: &'_ str, dialect: Option<LigatureFont>, state: State) -> (Token<'_>, &'_ str) {
+    let in_quote = state.in_quote;
+
     // handle empty input
     if input.is_empty() {
         // TODO?: maybe it's better to use something like `Token::End`
         return (Token::Space(""), "");
     }
 
+    // the name is spelled by its letters, even when it's also a word, e.g. `[_jan]`. anything
+    // that isn't a letter of toki pona is lexed as usual
+    if state.spelled_name {
+        let leftover = input.trim_start_matches(is_letter);
+        if leftover.len() < input.len() {
+            return (Token::Lasina(&input[..input.len() - leftover.len()]), leftover);
+        }
+    }
+
+    if let Some(profile) = dialect.map(LigatureFont::profile) {
+        // the font spells the name after the underscore by itself
+        if profile.names == Names::Spelled
+            && let Some(leftover) = input.strip_prefix("[_")
+            && leftover.starts_with(is_letter)
+        {
+            return (Token::LBrack, leftover);
+        }
+
+        if let Some((ascii, token)) = dialect_tokens(dialect, in_quote)
+            .find(|(ascii, _)| input.starts_with(ascii))
+        {
+            return (token, &input[ascii.len()..]);
+        }
+    }
+
     // parse single character modifier
     let mut iter = input.chars();
     let first = iter.next().unwrap_or_default();
@@ -112,10 +252,11 @@ pub fn next_token(input: &'_ str) -> (Token<'_>, &'_ str) {
             '+' => Token::Plus,
             '-' => Token::Minus,
             '_' => Token::Underscore,
-            '.' => Token::Dot,
+            // a dot that ends a sentence is punctuation, otherwise it's part of a name
+            '.' if leftover.starts_with(|c: char| !c.is_whitespace()) => Token::Dot,
             ':' => Token::Colon,
             '^' => Token::AltSymbol,
-            _ => break 'token None,
+            c => break 'token Punct::from_char(c).map(Token::Punct),
         })
     };
 
@@ -124,16 +265,44 @@ pub fn next_token(input: &'_ str) -> (Token<'_>, &'_ str) {
     }
 
     // parse space
-    let leftover = input.trim_start_matches(' ');
+    let leftover = input.trim_start_matches([' ', '\t']);
     let count = input.len() - leftover.len();
     if count > 0 {
         let token = Token::Space(&input[..count]);
         return (token, leftover);
     }
 
+    // parse newline
+    let leftover = input.trim_start_matches(['\r', '\n']);
+    let count = input.len() - leftover.len();
+    if count > 0 {
+        let token = Token::Newline(&input[..count]);
+        return (token, leftover);
+    }
+
+    // parse number
+    let leftover = input.trim_start_matches(|c: char| c.is_ascii_digit());
+    let count = input.len() - leftover.len();
+    if count > 0 {
+        let token = Token::Number(&input[..count]);
+        return (token, leftover);
+    }
+
     // parse word
     let leftover = input.trim_start_matches(|c: char| c.is_alphabetic());
-    let count = input.len() - leftover.len();
+    let mut count = input.len() - leftover.len();
+
+    // the word ends where a convention of the font that is made out of letters starts, e.g. the
+    // `zz` of `tokizzpona`
+    if let Some(end) = dialect_tokens(dialect, in_quote)
+        .filter_map(|(ascii, _)| input[..count].find(ascii))
+        .filter(|&end| end > 0)
+        .min()
+    {
+        count = end;
+    }
+
+    let leftover = &input[count..];
     if count > 0 {
         let text = &input[..count];
 
@@ -148,7 +317,7 @@ pub fn next_token(input: &'_ str) -> (Token<'_>, &'_ str) {
         let token = Word::from_str(text)
             .map(Token::Word)
             .unwrap_or_else(|_| {
-                if text.chars().all(|c| ALPHABET.contains(c.to_ascii_lowercase())) {
+                if text.chars().all(is_letter) {
                     Token::Lasina(text)
                 } else {
                     Token::Other(text)
@@ -167,18 +336,268 @@ pub fn next_token(input: &'_ str) -> (Token<'_>, &'_ str) {
     (token, leftover)
 }
 
+// the conventions of the ligature font that are written differently than in the input syntax
+fn dialect_tokens(
+    dialect: Option<LigatureFont>,
+    in_quote: bool,
+) -> impl Iterator<Item = (&'static str, Token<'static>)> {
+    let tokens = dialect.map(LigatureFont::profile).map(|profile| {
+        let quote = if in_quote { (profile.to, Token::To) } else { (profile.te, Token::Te) };
+        [
+            quote,
+            (profile.stacking_joiner, Token::Minus),
+            (profile.scaling_joiner, Token::Plus),
+            (profile.combining_long_glyph_extension, Token::Underscore),
+        ]
+    });
+
+    tokens
+        .into_iter()
+        .flatten()
+        .filter(|(ascii, token)| !ascii.is_empty() && *ascii != token.as_literal())
+}
+
 fn valid_char_token(c: char) -> bool {
-    c.is_alphabetic() || " ()[]{}+-_.:^".contains(c)
+    c.is_alphabetic() || c.is_ascii_digit() || " \t\r\n()[]{}+-_.:^".contains(c) || Punct::from_char(c).is_some()
+}
+
+pub fn tokens(input: &'_ str) -> impl Iterator<Item = Token<'_>> {
+    tokens_in(input, None)
 }
 
-pub fn tokens(mut input: &'_ str) -> impl Iterator<Item = Token<'_>> {
+// same as `tokens`, but also accepts the conventions of the ligature font
+pub fn tokens_in(mut input: &'_ str, dialect: Option<LigatureFont>) -> impl Iterator<Item = Token<'_>> {
+    let mut state = State::default();
     std::iter::from_fn(move || {
         if input.is_empty() {
             return None;
         }
 
         let token;
-        (token, input) = next_token(input);
+        (token, input) = next_token_in(input, dialect, &mut state);
         Some(token)
     })
 }
+
+// whether a quote of the font that is the same on both sides is still open at the end of the
+// input, so the text after it has to be read together with it for telling its quotes apart
+pub fn ends_in_quote(input: &str, dialect: Option<LigatureFont>) -> bool {
+    if dialect.is_none_or(|font| font.profile().te != font.profile().to) {
+        return false;
+    }
+
+    let mut state = State::default();
+    let mut rest = input;
+    while !rest.is_empty() {
+        (_, rest) = next_token_in(rest, dialect, &mut state);
+    }
+
+    state.in_quote
+}
+
+// a place in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    // in bytes
+    pub offset: usize,
+
+    // both are 1-based, and the column is counted in characters
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub const START: Self = Self {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+
+    // the location right after the given text
+    pub fn advance(mut self, text: &str) -> Self {
+        self.offset += text.len();
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        self
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+// the end is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spanned<'a> {
+    pub token: Token<'a>,
+    pub span: Span,
+}
+
+pub fn spanned_tokens(input: &'_ str) -> impl Iterator<Item = Spanned<'_>> {
+    spanned_tokens_at(input, Location::START)
+}
+
+// same as `spanned_tokens`, for when the input starts at the given location of a bigger text
+pub fn spanned_tokens_at(input: &'_ str, location: Location) -> impl Iterator<Item = Spanned<'_>> {
+    spanned_tokens_in(input, location, None)
+}
+
+// same as `spanned_tokens_at`, but also accepts the conventions of the ligature font
+pub fn spanned_tokens_in(
+    mut input: &'_ str,
+    mut location: Location,
+    dialect: Option<LigatureFont>,
+) -> impl Iterator<Item = Spanned<'_>> {
+    let mut state = State::default();
+    std::iter::from_fn(move || {
+        if input.is_empty() {
+            return None;
+        }
+
+        let (token, leftover) = next_token_in(input, dialect, &mut state);
+        let start = location;
+        location = location.advance(&input[..input.len() - leftover.len()]);
+        input = leftover;
+
+        let span = Span { start, end: location };
+        Some(Spanned { token, span })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(input: &str, dialect: LigatureFont) -> Vec<Token<'_>> {
+        tokens_in(input, Some(dialect)).filter(|token| !matches!(token, Token::Space(..))).collect()
+    }
+
+    // offset, line and column
+    type Place = (usize, usize, usize);
+
+    fn spans(input: &str, start: Location) -> Vec<(Token<'_>, Place, Place)> {
+        spanned_tokens_at(input, start)
+            .map(|Spanned { token, span }| {
+                let Span { start, end } = span;
+                (token, (start.offset, start.line, start.column), (end.offset, end.line, end.column))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn columns_are_counted_in_chars() {
+        // `ö` takes two bytes and `語` three, but a column each
+        assert_eq!(
+            spans("jan ö 語 li", Location::START),
+            [
+                (Token::Word(Word::Jan), (0, 1, 1), (3, 1, 4)),
+                (Token::Space(" "), (3, 1, 4), (4, 1, 5)),
+                (Token::Other("ö"), (4, 1, 5), (6, 1, 6)),
+                (Token::Space(" "), (6, 1, 6), (7, 1, 7)),
+                (Token::Other("語"), (7, 1, 7), (10, 1, 8)),
+                (Token::Space(" "), (10, 1, 8), (11, 1, 9)),
+                (Token::Word(Word::Li), (11, 1, 9), (13, 1, 11)),
+            ],
+        );
+
+        // a tab is a single column too
+        assert_eq!(spans("\tmi", Location::START)[1], (Token::Word(Word::Mi), (1, 1, 2), (3, 1, 4)));
+    }
+
+    #[test]
+    fn line_breaks_reset_the_column() {
+        assert_eq!(
+            spans("mi\n\nsina\r\na", Location::START),
+            [
+                (Token::Word(Word::Mi), (0, 1, 1), (2, 1, 3)),
+                (Token::Newline("\n\n"), (2, 1, 3), (4, 3, 1)),
+                (Token::Word(Word::Sina), (4, 3, 1), (8, 3, 5)),
+                (Token::Newline("\r\n"), (8, 3, 5), (10, 4, 1)),
+                (Token::Word(Word::A), (10, 4, 1), (11, 4, 2)),
+            ],
+        );
+
+        let end = Location::START.advance("jan ö\nli");
+        assert_eq!(end, Location { offset: 9, line: 2, column: 3 });
+        assert_eq!(end.to_string(), "2:3");
+    }
+
+    #[test]
+    fn spans_of_a_later_line() {
+        let start = Location::START.advance("mi toki.\nsina ");
+        assert_eq!(
+            spans("pona ö", start),
+            [
+                (Token::Word(Word::Pona), (14, 2, 6), (18, 2, 10)),
+                (Token::Space(" "), (18, 2, 10), (19, 2, 11)),
+                (Token::Other("ö"), (19, 2, 11), (21, 2, 12)),
+            ],
+        );
+
+        // the same as the spans of the whole text
+        let whole: Vec<_> = spanned_tokens("mi toki.\nsina pona ö").skip(7).map(|spanned| spanned.span).collect();
+        let later: Vec<_> = spanned_tokens_at("pona ö", start).map(|spanned| spanned.span).collect();
+        assert_eq!(whole, later);
+
+        // with the dialect, the spans cover the conventions of the font
+        let spanned: Vec<_> = spanned_tokens_in("tokizzpona", start, Some(LigatureFont::LinjaPona)).collect();
+        assert_eq!(spanned[1].token, Token::Minus);
+        assert_eq!((spanned[1].span.start.column, spanned[1].span.end.column), (10, 12));
+    }
+
+    #[test]
+    fn spelled_names() {
+        assert_eq!(lex("[_jan]", LigatureFont::NasinNanpa), [Token::LBrack, Token::Lasina("jan"), Token::RBrack]);
+        assert_eq!(lex("[_sonja]", LigatureFont::NasinNanpa), [Token::LBrack, Token::Lasina("sonja"), Token::RBrack]);
+
+        // only the letters of toki pona are spelled
+        assert_eq!(lex("[_ö]", LigatureFont::NasinNanpa), [Token::LBrack, Token::Underscore, Token::Other("ö"), Token::RBrack]);
+        assert_eq!(lex("[_Жan]", LigatureFont::NasinNanpa)[..2], [Token::LBrack, Token::Underscore]);
+        assert_eq!(lex("[_janö]", LigatureFont::NasinNanpa), [Token::LBrack, Token::Lasina("jan"), Token::Other("ö"), Token::RBrack]);
+
+        // only right after the `[_` of the font
+        assert_eq!(lex("[jan]", LigatureFont::NasinNanpa), [Token::LBrack, Token::Word(Word::Jan), Token::RBrack]);
+        assert_eq!(lex("[_jan]", LigatureFont::LinjaPona)[..2], [Token::LBrack, Token::Underscore]);
+    }
+
+    #[test]
+    fn quotes_that_are_the_same_on_both_sides() {
+        let dialect = LigatureFont::LinjaSike;
+        assert_eq!(lex("\"a\" \"a\"", dialect), [Token::Te, Token::Word(Word::A), Token::To, Token::Te, Token::Word(Word::A), Token::To]);
+
+        assert!(ends_in_quote("mi toki e \"toki\n", Some(dialect)));
+        assert!(!ends_in_quote("\"toki\" li pona\n", Some(dialect)));
+        assert!(!ends_in_quote("\"toki\n", None));
+
+        // the quotes of the other fonts are told apart by themselves
+        assert!(!ends_in_quote("te toki\n", Some(LigatureFont::NasinNanpa)));
+        assert!(!ends_in_quote("«toki\n", Some(LigatureFont::LinjaPona)));
+    }
+
+    #[test]
+    fn state_carries_over() {
+        let mut state = State::default();
+        let (token, rest) = next_token_in("\"toki", Some(LigatureFont::LinjaSike), &mut state);
+        assert_eq!((token, rest), (Token::Te, "toki"));
+        assert!(state.in_quote);
+
+        next_token_in("toki", Some(LigatureFont::LinjaSike), &mut state);
+        assert_eq!(next_token_in("\"", Some(LigatureFont::LinjaSike), &mut state).0, Token::To);
+        assert!(!state.in_quote);
+    }
+}
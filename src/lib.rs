@@ -0,0 +1,26 @@
+pub mod braille;
+pub mod check;
+pub mod cluster;
+pub mod convert;
+pub mod diagnostic;
+pub mod emosi;
+pub mod font;
+pub mod interlinear;
+pub mod json;
+pub mod layout;
+pub mod lexer;
+pub mod ligature;
+pub mod lint;
+pub mod modifier;
+pub mod normalize;
+pub mod number;
+pub mod punctuation;
+pub mod search;
+pub mod show;
+pub mod source_map;
+pub mod spacing;
+pub mod strict;
+pub mod suggest;
+pub mod width;
+pub mod word;
+pub mod word_set;
@@ -0,0 +1,324 @@
+use std::io;
+
+use crate::lexer::{self, Token};
+use crate::modifier::Modifier;
+use crate::show::{self, Style};
+use crate::word::Word;
+use crate::word_set::Fallback;
+
+// fonts that turn ASCII text into glyphs with ligatures, for apps without support for the
+// Private Use Area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LigatureFont {
+    NasinNanpa,
+    LinjaSike,
+    LinjaPona,
+}
+
+impl LigatureFont {
+    pub const fn profile(self) -> &'static Profile {
+        match self {
+            Self::NasinNanpa => &NASIN_NANPA,
+            Self::LinjaSike => &LINJA_SIKE,
+            Self::LinjaPona => &LINJA_PONA,
+        }
+    }
+}
+
+impl std::str::FromStr for LigatureFont {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "nasin-nanpa" => Ok(Self::NasinNanpa),
+            "linja-sike" => Ok(Self::LinjaSike),
+            "linja-pona" => Ok(Self::LinjaPona),
+            _ => Err(()),
+        }
+    }
+}
+
+// how names are written inside of cartouches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Names {
+    // the name itself after an underscore, e.g. `[_sonja]`, which the font spells by itself
+    Spelled,
+
+    // a word for every letter of the name, whose glyph stands for the letter, e.g.
+    // `[sona open nena jelo ante]`
+    Glyphs,
+}
+
+// the ASCII that a font turns into each of the modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub start_of_cartouche: &'static str,
+    pub end_of_cartouche: &'static str,
+    pub stacking_joiner: &'static str,
+    pub scaling_joiner: &'static str,
+    pub start_of_long_glyph: &'static str,
+    pub end_of_long_glyph: &'static str,
+    pub combining_long_glyph_extension: &'static str,
+    pub start_of_reverse_long_glyph: &'static str,
+    pub end_of_reverse_long_glyph: &'static str,
+    pub middle_dot: &'static str,
+    pub colon: &'static str,
+
+    // quotes, and the alternative form of the glyph before, when the font has them
+    pub te: &'static str,
+    pub to: &'static str,
+    pub alt_symbol: Option<&'static str>,
+
+    pub names: Names,
+}
+
+// from the documentation of nasin nanpa (https://github.com/ETBCOR/nasin-nanpa), which also
+// accepts `[_name]` for spelling a name by its letters
+pub const NASIN_NANPA: Profile = Profile {
+    start_of_cartouche: "[",
+    end_of_cartouche: "]",
+    stacking_joiner: "-",
+    scaling_joiner: "+",
+    start_of_long_glyph: "(",
+    end_of_long_glyph: ")",
+    combining_long_glyph_extension: "_",
+    start_of_reverse_long_glyph: "{",
+    end_of_reverse_long_glyph: "}",
+    middle_dot: ".",
+    colon: ":",
+    te: "te",
+    to: "to",
+    alt_symbol: Some("^"),
+    names: Names::Spelled,
+};
+
+// from the list of ligatures that comes with linja sike, which has no reverse long glyphs and
+// no alternative forms, and spells names with the words of their letters
+pub const LINJA_SIKE: Profile = Profile {
+    start_of_cartouche: "[",
+    end_of_cartouche: "]",
+    stacking_joiner: "-",
+    scaling_joiner: "+",
+    start_of_long_glyph: "(",
+    end_of_long_glyph: ")",
+    combining_long_glyph_extension: "_",
+    start_of_reverse_long_glyph: "(",
+    end_of_reverse_long_glyph: ")",
+    middle_dot: ".",
+    colon: ":",
+    te: "\"",
+    to: "\"",
+    alt_symbol: None,
+    names: Names::Glyphs,
+};
+
+// from the documentation of linja pona (https://musilili.net/linja-pona/), where `zz` stacks the
+// glyphs around it and « » are the quotes
+pub const LINJA_PONA: Profile = Profile {
+    start_of_cartouche: "[",
+    end_of_cartouche: "]",
+    stacking_joiner: "zz",
+    scaling_joiner: "+",
+    start_of_long_glyph: "(",
+    end_of_long_glyph: ")",
+    combining_long_glyph_extension: "_",
+    start_of_reverse_long_glyph: "(",
+    end_of_reverse_long_glyph: ")",
+    middle_dot: ".",
+    colon: ":",
+    te: "«",
+    to: "»",
+    alt_symbol: None,
+    names: Names::Glyphs,
+};
+
+impl Profile {
+    pub const fn modifier(&self, modifier: Modifier) -> &'static str {
+        match modifier {
+            Modifier::StartOfCartouche => self.start_of_cartouche,
+            Modifier::EndOfCartouche => self.end_of_cartouche,
+            Modifier::StackingJoiner => self.stacking_joiner,
+            Modifier::ScalingJoiner => self.scaling_joiner,
+            Modifier::StartOfLongGlyph => self.start_of_long_glyph,
+            Modifier::EndOfLongGlyph => self.end_of_long_glyph,
+            Modifier::CombiningLongGlyphExtension => self.combining_long_glyph_extension,
+            Modifier::StartOfReverseLongGlyph => self.start_of_reverse_long_glyph,
+            Modifier::EndOfReverseLongGlyph => self.end_of_reverse_long_glyph,
+            Modifier::MiddleDot => self.middle_dot,
+            Modifier::Colon => self.colon,
+        }
+    }
+
+    // the ASCII of the token, when it's written as a modifier in sitelen UCSUR
+    fn token(&self, token: Token) -> Option<&'static str> {
+        match token {
+            Token::Te => Some(self.te),
+            Token::To => Some(self.to),
+            Token::AltSymbol => Some(self.alt_symbol.unwrap_or_default()),
+            _ => {
+                let (_, c) = show::TOKEN_MODIFIER.iter().find(|(t, _)| *t == token)?;
+                Modifier::from_sitelen(*c).map(|modifier| self.modifier(modifier))
+            }
+        }
+    }
+
+    // the contents of the cartouche of the name
+    fn name(&self, name: &str) -> String {
+        match self.names {
+            Names::Spelled => format!("_{}", name.to_lowercase()),
+            Names::Glyphs => {
+                let words: Vec<&str> = name
+                    .chars()
+                    .filter_map(|c| show::letter_glyph(c).and_then(Word::from_sitelen))
+                    .map(Word::as_lasina)
+                    .collect();
+
+                words.join(" ")
+            }
+        }
+    }
+}
+
+// the word when it's written in sitelen Lasina by the fallback, with a ZERO WIDTH NON-JOINER
+// after its first letter so the font doesn't turn it into a glyph
+fn lasina(word: Word) -> String {
+    let (first, rest) = word.as_lasina().split_at(1);
+    format!("{first}\u{200c}{rest}")
+}
+
+// writes the sitelen Lasina input as the ASCII that the font turns into glyphs. words are
+// separated by single spaces, and modifiers are written next to the words they modify. words
+// that aren't in the word set of the style are written with its fallback
+pub fn to_ligatures(
+    out: &mut impl io::Write,
+    input: &str,
+    profile: &Profile,
+    style: Style,
+) -> io::Result<()> {
+    let mut tokens = lexer::tokens_in(input, style.dialect).peekable();
+    let mut prev: Option<Token> = None;
+    let mut cartouche_depth = 0;
+    while let Some(token) = tokens.next() {
+        if let Token::Space(..) = token {
+            let next = tokens.peek();
+            if prev.is_some_and(|prev| !prev.attaches_right() && !matches!(prev, Token::Newline(..)))
+                && next.is_some_and(|next| !next.attaches_left() && !matches!(next, Token::Newline(..)))
+            {
+                write!(out, " ")?;
+            }
+
+            continue;
+        }
+
+        match token {
+            Token::LBrack => cartouche_depth += 1,
+            Token::RBrack => cartouche_depth -= usize::from(cartouche_depth > 0),
+            _ => {}
+        }
+
+        match token {
+            Token::Lasina(name) if cartouche_depth > 0 => write!(out, "{}", profile.name(name))?,
+            Token::Lasina(name) => write!(
+                out,
+                "{}{}{}",
+                profile.start_of_cartouche,
+                profile.name(name),
+                profile.end_of_cartouche,
+            )?,
+            Token::Word(word) if style.words.contains(word) => write!(out, "{}", word.as_lasina())?,
+            Token::Word(word) if style.fallback == Fallback::Lasina => write!(out, "{}", lasina(word))?,
+            Token::Word(word) if cartouche_depth > 0 => write!(out, "{}", profile.name(word.as_lasina()))?,
+            Token::Word(word) => write!(
+                out,
+                "{}{}{}",
+                profile.start_of_cartouche,
+                profile.name(word.as_lasina()),
+                profile.end_of_cartouche,
+            )?,
+            Token::Number(digits) => {
+                match style.number_words(digits) {
+                    Some(words) => {
+                        let words: Vec<&str> = words.iter().map(|word| word.as_lasina()).collect();
+                        write!(out, "{}", words.join(" "))?
+                    }
+                    None => write!(out, "{digits}")?,
+                }
+            }
+            Token::Punct(punct) => write!(out, "{}", punct.as_ascii())?,
+            token => match profile.token(token) {
+                Some(ascii) => write!(out, "{ascii}")?,
+                None => write!(out, "{}", token.as_literal())?,
+            },
+        }
+
+        prev = Some(token);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::NumberSystem;
+    use crate::word_set::WordSet;
+
+    fn ligatures(input: &str, font: LigatureFont, style: Style) -> String {
+        let mut output = Vec::new();
+        to_ligatures(&mut output, input, font.profile(), style).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn profiles() {
+        let input = "toki-pona li pona tawa [Sonja] te a to";
+        let style = Style::default();
+        assert_eq!(
+            ligatures(input, LigatureFont::NasinNanpa, style),
+            "toki-pona li pona tawa [_sonja] te a to"
+        );
+        assert_eq!(
+            ligatures(input, LigatureFont::LinjaSike, style),
+            "toki-pona li pona tawa [sona open nena jelo ante] \" a \""
+        );
+        assert_eq!(
+            ligatures(input, LigatureFont::LinjaPona, style),
+            "tokizzpona li pona tawa [sona open nena jelo ante] « a »"
+        );
+    }
+
+    #[test]
+    fn words_outside_of_the_set_use_the_fallback() {
+        let style = Style {
+            words: WordSet::Pu,
+            ..Style::default()
+        };
+        assert_eq!(ligatures("soko li pona", LigatureFont::NasinNanpa, style), "[_soko] li pona");
+        assert_eq!(ligatures("[soko]", LigatureFont::NasinNanpa, style), "[_soko]");
+        assert_eq!(
+            ligatures("soko", LigatureFont::LinjaPona, style),
+            "[sona open kalama open]"
+        );
+
+        let style = Style {
+            fallback: Fallback::Lasina,
+            ..style
+        };
+        assert_eq!(ligatures("soko li pona", LigatureFont::NasinNanpa, style), "s\u{200c}oko li pona");
+    }
+
+    #[test]
+    fn numbers() {
+        let style = Style {
+            numbers: Some(NumberSystem::Pona),
+            ..Style::default()
+        };
+        assert_eq!(ligatures("jan 7", LigatureFont::NasinNanpa, style), "jan luka tu");
+
+        let style = Style {
+            words: WordSet::only(Word::ALL.iter().copied().filter(|&word| word != Word::Luka)),
+            ..style
+        };
+        assert_eq!(ligatures("jan 7", LigatureFont::NasinNanpa, style), "jan 7");
+    }
+}
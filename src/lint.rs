@@ -0,0 +1,181 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{self, Location, Spanned, Token};
+use crate::ligature::LigatureFont;
+
+// finds the structural problems of the input, e.g. brackets that are never closed. the input is
+// read with the conventions of the dialect, like the conversion does
+pub fn lint(input: &str, dialect: Option<LigatureFont>) -> Vec<Diagnostic> {
+    let tokens: Vec<Spanned> = lexer::spanned_tokens_in(input, Location::START, dialect).collect();
+
+    let mut diagnostics = Vec::new();
+    let mut report = |code, Spanned { token, span }: Spanned, message: &str| {
+        diagnostics.push(Diagnostic {
+            code,
+            span,
+            message: format!("`{}` {message}", token.as_literal()),
+            help: None,
+        })
+    };
+
+    // the opening tokens that haven't been closed yet
+    let mut open: Vec<Spanned> = Vec::new();
+
+    for (i, &spanned) in tokens.iter().enumerate() {
+        let token = spanned.token;
+
+        // the closest tokens on each side, ignoring spaces
+        let prev = tokens[..i].iter().rev().find(|s| !matches!(s.token, Token::Space(..)));
+        let next = tokens[i + 1..].iter().find(|s| !matches!(s.token, Token::Space(..)));
+
+        match token {
+            Token::LBrack if open.iter().any(|s| s.token == Token::LBrack) => {
+                report("nested-cartouche", spanned, "starts a cartouche inside of a cartouche");
+                open.push(spanned);
+            }
+
+            Token::LParen | Token::LBrack | Token::LBrace | Token::Te => open.push(spanned),
+
+            Token::RParen | Token::RBrack | Token::RBrace | Token::To => {
+                if open.last().is_some_and(|s| closing(s.token) == Some(token)) {
+                    open.pop();
+                } else if let Some(index) = open.iter().rposition(|s| closing(s.token) == Some(token)) {
+                    // the brackets cross, so the ones inside are closed with it
+                    let inner = open.last().expect("there's a token after the one it closes").token;
+                    let message = format!(
+                        "closes `{}` before `{}` is closed",
+                        open[index].token.as_literal(),
+                        inner.as_literal(),
+                    );
+
+                    report("mismatched-delimiter", spanned, &message);
+                    open.truncate(index);
+                } else if token == Token::To {
+                    report("unmatched-quote", spanned, "has no `te` before it");
+                } else {
+                    report("unbalanced-delimiter", spanned, "has nothing to close");
+                }
+            }
+
+            Token::Plus | Token::Minus => {
+                if !prev.is_some_and(|s| matches!(s.token, Token::Word(..) | Token::AltSymbol)) {
+                    report("dangling-joiner", spanned, "has no word before it");
+                }
+
+                if !next.is_some_and(|s| matches!(s.token, Token::Word(..))) {
+                    report("dangling-joiner", spanned, "has no word after it");
+                }
+            }
+
+            Token::AltSymbol
+                if !i.checked_sub(1).is_some_and(|i| matches!(tokens[i].token, Token::Word(..))) =>
+            {
+                report("misplaced-alt-symbol", spanned, "doesn't follow a word");
+            }
+
+            Token::Underscore
+                if !open.iter().any(|s| matches!(s.token, Token::LParen | Token::LBrace)) =>
+            {
+                report("extension-outside-long-glyph", spanned, "is outside of a long glyph");
+            }
+
+            _ => {}
+        }
+    }
+
+    for spanned in open {
+        match spanned.token {
+            Token::Te => report("unmatched-quote", spanned, "has no `to` after it"),
+            _ => report("unbalanced-delimiter", spanned, "is never closed"),
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start.offset);
+    diagnostics
+}
+
+fn closing(token: Token) -> Option<Token> {
+    Some(match token {
+        Token::LParen => Token::RParen,
+        Token::LBrack => Token::RBrack,
+        Token::LBrace => Token::RBrace,
+        Token::Te => Token::To,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the code and the `line:column` of every diagnostic
+    fn codes(input: &str) -> Vec<(&'static str, String)> {
+        lint(input, None)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.span.start.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_input_has_no_diagnostics() {
+        assert_eq!(codes("jan [Sonja] li toki-pona e (toki_ala) te mi^ to\n"), []);
+    }
+
+    #[test]
+    fn unbalanced_delimiters() {
+        assert_eq!(codes("(toki"), [("unbalanced-delimiter", "1:1".into())]);
+        assert_eq!(codes("toki\npona}"), [("unbalanced-delimiter", "2:5".into())]);
+        assert_eq!(codes("[toki)"), [
+            ("unbalanced-delimiter", "1:1".into()),
+            ("unbalanced-delimiter", "1:6".into()),
+        ]);
+    }
+
+    #[test]
+    fn crossing_delimiters() {
+        assert_eq!(codes("(pi [toki)"), [("mismatched-delimiter", "1:10".into())]);
+        assert_eq!(codes("te (toki to)"), [
+            ("mismatched-delimiter", "1:10".into()),
+            ("unbalanced-delimiter", "1:12".into()),
+        ]);
+
+        let message = &lint("{mi (toki}", None)[0].message;
+        assert_eq!(message, "`}` closes `{` before `(` is closed");
+    }
+
+    #[test]
+    fn dialects() {
+        let dialect = |input, font| -> Vec<&'static str> {
+            lint(input, Some(font)).into_iter().map(|diagnostic| diagnostic.code).collect()
+        };
+
+        assert_eq!(dialect("jan [_sonja] li toki e \"toki pona\"", LigatureFont::NasinNanpa), [] as [&str; 0]);
+        assert_eq!(dialect("mi toki e \"toki pona\"", LigatureFont::LinjaSike), [] as [&str; 0]);
+        assert_eq!(dialect("mi toki e \"toki", LigatureFont::LinjaSike), ["unmatched-quote"]);
+
+        // without the dialect, the underscore is outside of a long glyph
+        assert_eq!(codes("jan [_sonja]"), [("extension-outside-long-glyph", "1:6".into())]);
+    }
+
+    #[test]
+    fn unmatched_quotes() {
+        assert_eq!(codes("te toki"), [("unmatched-quote", "1:1".into())]);
+        assert_eq!(codes("toki to"), [("unmatched-quote", "1:6".into())]);
+    }
+
+    #[test]
+    fn dangling_joiners() {
+        assert_eq!(codes("-toki"), [("dangling-joiner", "1:1".into())]);
+        assert_eq!(codes("toki +"), [("dangling-joiner", "1:6".into())]);
+    }
+
+    #[test]
+    fn misplaced_alt_symbols_and_extensions() {
+        assert_eq!(codes("toki ^"), [("misplaced-alt-symbol", "1:6".into())]);
+        assert_eq!(codes("toki_pona"), [("extension-outside-long-glyph", "1:5".into())]);
+    }
+
+    #[test]
+    fn nested_cartouches() {
+        assert_eq!(codes("[jan [Sonja]]"), [("nested-cartouche", "1:6".into())]);
+    }
+}
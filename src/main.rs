@@ -1,100 +1,721 @@
 use std::env;
-use std::io;
+use std::fs::File;
+use std::io::{self, Read as _, Write as _};
 
-use crate::lexer::Token;
+use sitelen_ucsur::convert::{converted_tokens, from_sitelen, to_sitelen};
+use sitelen_ucsur::diagnostic::Diagnostic;
+use sitelen_ucsur::braille::{self, sitelen_to_braille, to_braille};
+use sitelen_ucsur::emosi::{from_emosi, to_emosi};
+use sitelen_ucsur::font::Font;
+use sitelen_ucsur::layout::{self, Direction};
+use sitelen_ucsur::lexer::{self, Location};
+use sitelen_ucsur::ligature::{LigatureFont, to_ligatures};
+use sitelen_ucsur::modifier::{UNICODE_NAMES, UNICODE_TABLE, VARIATION_SELECTORS};
+use sitelen_ucsur::normalize::normalize;
+use sitelen_ucsur::{check, interlinear, json, lint, suggest, width};
+use sitelen_ucsur::search::{self, Found};
+use sitelen_ucsur::punctuation::{Punct, Punctuation};
+use sitelen_ucsur::show::{ModifierSet, Style};
+use sitelen_ucsur::source_map::SourceMap;
+use sitelen_ucsur::spacing::Spacing;
+use sitelen_ucsur::strict::Strict;
+use sitelen_ucsur::word::{Book, Status, Word};
+use sitelen_ucsur::word_set::{self, Fallback, WordSet};
 
-mod show;
-mod lexer;
-mod word;
-mod modifier;
+enum Mode {
+    LasinaToSitelen,
+    SitelenToLasina,
+
+    // report the problems of the sitelen Lasina input instead of converting it
+    Lint,
+
+    // report the problems of the sitelen UCSUR input instead of converting it
+    Check,
+
+    // rewrite the sitelen UCSUR input into its canonical form
+    Normalize,
+
+    // print the meanings of the given word
+    Define(String),
+
+    // print the words and phrases that mean the given English
+    Search(String),
+
+    // print the words of the dictionary with their metadata
+    Words,
+
+    // print what the font of `--font` has glyphs for
+    Coverage,
+}
+
+impl Mode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::LasinaToSitelen => "from",
+            Self::SitelenToLasina => "to",
+            Self::Lint => "lint",
+            Self::Check => "check",
+            Self::Normalize => "normalize",
+            Self::Define(..) => "define",
+            Self::Search(..) => "search",
+            Self::Words => "words",
+            Self::Coverage => "coverage",
+        }
+    }
+}
+
+// how the output of the conversion from sitelen Lasina is written
+enum Format {
+    Text,
+
+    // a single array of tokens
+    Json,
+
+    // a token per line
+    JsonLines,
+
+    // every line twice, the sitelen Lasina above the sitelen UCSUR
+    Interlinear,
+
+    // HTML with the sitelen Lasina as ruby annotations
+    Html,
+}
+
+struct Args {
+    mode: Mode,
+    format: Format,
+    style: Style,
+
+    // report unknown words and text instead of passing them through
+    strict: bool,
+
+    // where to write the source map of the conversion as JSON
+    source_map: Option<String>,
+
+    // the number of columns to wrap the lines of the output at
+    width: Option<usize>,
+
+    // the height of the columns to lay the output out in, instead of lines
+    vertical: Option<usize>,
+    direction: Direction,
+
+    // write the meanings of the words under their glyphs
+    gloss: bool,
+
+    // which of the words to print with `words`
+    book: Option<Book>,
+    status: Option<Status>,
+    ucsur_version: Option<u16>,
+
+    // the font file to only use the glyphs of
+    font: Option<String>,
+
+    // write the ASCII that the font turns into glyphs, instead of sitelen UCSUR or the input syntax
+    ligatures: Option<LigatureFont>,
+
+    // write sitelen Emosi instead of sitelen UCSUR, or read it instead of sitelen UCSUR with `to`
+    emosi: bool,
+
+    // write Unicode braille instead of sitelen UCSUR, or instead of sitelen Lasina with `to`
+    braille: bool,
+
+    // the options that were given that only some of the modes use
+    mode_options: Vec<&'static str>,
+}
 
 fn main() {
-    let lasina_to_sitelen = parse_args();
+    let mut args = parse_args();
+
+    let font = args.font.as_ref().map(|path| {
+        let data = std::fs::read(path).unwrap_or_else(|err| {
+            eprintln!("error: can't read `{path}`: {err}");
+            std::process::exit(1)
+        });
+
+        Font::parse(&data).unwrap_or_else(|| {
+            eprintln!("error: `{path}` isn't a font with a Unicode `cmap` table");
+            std::process::exit(1)
+        })
+    });
+
+    // only what the font has glyphs for is written as glyphs. the words it doesn't have are
+    // written with the fallback, and the rest falls back to ASCII
+    if let Some(font) = &font {
+        let style = &mut args.style;
+        style.words = WordSet::only(font.words().filter(|&word| style.words.contains(word)));
+        style.modifiers = ModifierSet::only(|c| font.supports(c));
+        style.alternatives = WordSet::only(
+            font.words().filter(|word| font.supports_variation(word.as_sitelen(), '\u{fe00}')),
+        );
+
+        let puncts = [Punct::FullStop, Punct::Comma, Punct::Exclamation, Punct::Question];
+        if !puncts.iter().all(|&punct| font.supports(style.punctuation.as_sitelen(punct))) {
+            style.punctuation = Punctuation::Ascii;
+        }
+
+        style.spacing = match style.spacing {
+            Spacing::Double if !font.supports('\u{3000}') => Spacing::None,
+            Spacing::Ideographic if !font.supports('\u{3000}') => Spacing::Ascii,
+            Spacing::ZeroWidth if !font.supports('\u{200b}') => Spacing::Ascii,
+            spacing => spacing,
+        };
+    }
 
     let mut stdout = io::stdout();
-    let res = if lasina_to_sitelen {
-        main_loop(&mut stdout, to_sitelen)
-    } else {
-        main_loop(&mut stdout, from_sitelen)
+    let mut strict = args.strict.then(|| Strict::new(args.style.dialect));
+    let mut source_map = args.source_map.is_some().then(SourceMap::default);
+
+    // the file is opened before anything is converted, so a bad path is reported right away
+    let mut source_map_file = args.source_map.as_ref().map(|path| {
+        let file = File::create(path).unwrap_or_else(|err| {
+            eprintln!("error: can't write the source map to `{path}`: {err}");
+            std::process::exit(1)
+        });
+
+        (path, io::BufWriter::new(file))
+    });
+
+    let mut failed = false;
+    let mut location = Location::START;
+    let mut check = |input: &str| {
+        let mut diagnostics = strict.as_mut().map(|strict| strict.check(input)).unwrap_or_default();
+        if args.style.fallback == Fallback::Error {
+            diagnostics.extend(word_set::check(input, location, args.style));
+        }
+
+        if args.braille {
+            diagnostics.extend(braille::check(input, location, args.style));
+        }
+
+        location = location.advance(input);
+        for diagnostic in diagnostics {
+            eprintln!("error[{}]: {diagnostic}", diagnostic.code);
+            failed = true;
+        }
+    };
+
+    let res = match (&args.mode, &args.format) {
+        (Mode::LasinaToSitelen, Format::Text) if let Some(height) = args.vertical => {
+            // the columns go across the lines, so the whole input is needed
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).and_then(|_| {
+                check(&input);
+
+                let mut output = Vec::new();
+                to_sitelen(&mut output, &input, args.style, None)?;
+                let output = String::from_utf8(output).expect("the output is always valid UTF-8");
+                stdout.write_all(layout::vertical(&output, height, args.direction).as_bytes())
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Text) if let Some(font) = args.ligatures => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                to_ligatures(out, input, font.profile(), args.style)
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Text) if args.emosi => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                to_emosi(out, input, args.style)
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Text) if args.braille => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                to_braille(out, input, args.style)
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Text) if args.gloss => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                interlinear::write_glossed(out, input, args.style)
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Text) => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                let Some(width) = args.width else {
+                    return to_sitelen(out, input, args.style, source_map.as_mut());
+                };
+
+                let mut output = Vec::new();
+                to_sitelen(&mut output, input, args.style, None)?;
+                let output = String::from_utf8(output).expect("the output is always valid UTF-8");
+                out.write_all(width::wrap(&output, width).as_bytes())
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Json) => json_loop(&mut stdout, false, args.style, check),
+        (Mode::LasinaToSitelen, Format::JsonLines) => {
+            json_loop(&mut stdout, true, args.style, check)
+        }
+        (Mode::LasinaToSitelen, Format::Interlinear) => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                interlinear::write_text(out, input, args.style, args.gloss)
+            })
+        }
+        (Mode::LasinaToSitelen, Format::Html) => {
+            main_loop(&mut stdout, args.style.dialect, |out, input| {
+                check(input);
+                interlinear::write_html(out, input, args.style)
+            })
+        }
+        (Mode::SitelenToLasina, _) if let Some(font) = args.ligatures => {
+            main_loop(&mut stdout, None, |out, input| {
+                let mut lasina = Vec::new();
+                from_sitelen(&mut lasina, input, args.style)?;
+                let lasina = String::from_utf8(lasina).expect("the output is always valid UTF-8");
+                to_ligatures(out, &lasina, font.profile(), args.style)
+            })
+        }
+        (Mode::SitelenToLasina, _) if args.emosi => main_loop(&mut stdout, None, from_emosi),
+        (Mode::SitelenToLasina, _) if args.braille => {
+            let mut location = Location::START;
+            main_loop(&mut stdout, None, |out, input| {
+                for diagnostic in braille::check_sitelen(input, location, args.style) {
+                    eprintln!("error[{}]: {diagnostic}", diagnostic.code);
+                    failed = true;
+                }
+
+                location = location.advance(input);
+                sitelen_to_braille(out, input, args.style)
+            })
+        }
+        (Mode::SitelenToLasina, _) => {
+            main_loop(&mut stdout, None, |out, input| from_sitelen(out, input, args.style))
+        }
+        (Mode::Lint, _) => run_diagnostics(&mut stdout, |input| lint::lint(input, args.style.dialect))
+            .map(|found| failed = found),
+        (Mode::Normalize, _) => main_loop(&mut stdout, None, normalize),
+        (Mode::Check, _) => run_diagnostics(&mut stdout, check::check).map(|found| failed = found),
+        (Mode::Define(word), _) => define(&mut stdout, word).map(|found| failed = !found),
+        (Mode::Search(english), _) => search(&mut stdout, english).map(|found| failed = !found),
+        (Mode::Words, _) => list_words(&mut stdout, &args),
+        (Mode::Coverage, _) => match &font {
+            Some(font) => coverage(&mut stdout, font),
+            None => {
+                eprintln!("error: `coverage` needs a `--font`");
+                std::process::exit(1)
+            }
+        },
     };
 
-    res.unwrap();
+    if let Err(err) = res {
+        eprintln!("error: {err}");
+        std::process::exit(1)
+    }
+
+    if let Some(((path, file), source_map)) = source_map_file.as_mut().zip(source_map)
+        && let Err(err) = source_map.write_json(file).and_then(|_| file.flush())
+    {
+        eprintln!("error: can't write the source map to `{path}`: {err}");
+        std::process::exit(1)
+    }
+
+    if failed {
+        std::process::exit(1)
+    }
 }
 
-fn main_loop<F, O>(out: &mut O, mut conv: F) -> io::Result<()>
+// the most lines that are held back while a quote of the dialect is still open
+const MAX_QUOTE_LINES: usize = 16;
+
+// converts the input a line at a time, except that a quote of the dialect that is still open at
+// the end of a line is converted together with the lines after it, up to the one that closes it.
+// a blank line, or too many lines, ends the quote anyway, so a stray quote doesn't hold back the
+// rest of the output
+fn main_loop<F, O>(out: &mut O, dialect: Option<LigatureFont>, mut conv: F) -> io::Result<()>
 where
     F: FnMut(&mut O, &str) -> io::Result<()>,
     O: io::Write
 {
     let stdin = io::stdin();
-    let mut line = String::new();
-    while stdin.read_line(&mut line).is_ok() {
-        if line.is_empty() {
+    let mut input = String::new();
+    let mut lines = 0;
+    loop {
+        let start = input.len();
+        let read = stdin.read_line(&mut input)?;
+        if input.is_empty() {
             break
         }
 
-        conv(out, &line)?;
+        lines += 1;
+        let blank = input[start..].trim().is_empty();
+        if read > 0 && !blank && lines < MAX_QUOTE_LINES && lexer::ends_in_quote(&input, dialect) {
+            continue;
+        }
+
+        conv(out, &input)?;
         out.flush()?;
 
-        line.clear();
+        input.clear();
+        lines = 0;
     }
 
     Ok(())
 }
 
-fn to_sitelen(out: &mut impl io::Write, input: &str) -> io::Result<()> {
-    let mut prev_is_word = false;
-    for token in lexer::tokens(input) {
-        match token {
-            Token::Word(..) => prev_is_word = true,
-            Token::Other(..) => prev_is_word = false,
-            Token::Space(spaces) if !prev_is_word => {
-                write!(out, "{}", spaces)?;
-                continue
+// writes the tokens of the input as JSON, either as a single array or as a token per line
+fn json_loop(
+    out: &mut impl io::Write,
+    lines: bool,
+    style: Style,
+    mut check: impl FnMut(&str),
+) -> io::Result<()> {
+    if !lines {
+        write!(out, "[")?;
+    }
+
+    let mut location = Location::START;
+    let mut first = true;
+    main_loop(out, style.dialect, |out, input| {
+        check(input);
+
+        for (spanned, output) in converted_tokens(input, location, style) {
+            if !lines && !first {
+                write!(out, ",")?;
+            }
+
+            json::write_token(out, &spanned, &output)?;
+            first = false;
+
+            if lines {
+                writeln!(out)?;
             }
-            _ => {}
         }
 
-        write!(out, "{}", token)?;
+        location = location.advance(input);
+        Ok(())
+    })?;
+
+    if !lines {
+        writeln!(out, "]")?;
     }
 
     Ok(())
 }
 
+// returns whether any problems were found
+fn run_diagnostics(
+    out: &mut impl io::Write,
+    find: impl FnOnce(&str) -> Vec<Diagnostic>,
+) -> io::Result<bool> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    let diagnostics = find(&input);
+    for diagnostic in &diagnostics {
+        let Diagnostic { code, span, message, .. } = diagnostic;
+        writeln!(out, "{}-{}: {code}: {message}", span.start, span.end)?;
+    }
+
+    Ok(!diagnostics.is_empty())
+}
 
-fn from_sitelen(out: &mut impl io::Write, input: &str) -> io::Result<()> {
-    for c in input.chars() {
-        if let Some(token) = Token::from_sitelen(c) {
-            write!(out, "{} ", token.as_literal())?;
-        } else {
-            write!(out, "{}", c)?;
+// returns whether the word is in the dictionary
+fn define(out: &mut impl io::Write, word: &str) -> io::Result<bool> {
+    let Ok(word) = word.parse::<Word>() else {
+        match suggest::did_you_mean(word) {
+            Some(help) => eprintln!("error: unknown word `{word}`, {help}"),
+            None => eprintln!("error: unknown word `{word}`"),
         }
+
+        return Ok(false);
+    };
+
+    writeln!(out, "{} {}: {}", word.as_lasina(), word.as_sitelen(), word.gloss())?;
+    Ok(true)
+}
+
+// returns whether anything was found
+fn search(out: &mut impl io::Write, english: &str) -> io::Result<bool> {
+    let found = search::search(english);
+    if found.is_empty() {
+        eprintln!("error: nothing means `{english}`");
+    }
+
+    for Found { words, gloss, .. } in &found {
+        let lasina: Vec<&str> = words.iter().map(|word| word.as_lasina()).collect();
+        let sitelen: String = words.iter().map(|word| word.as_sitelen()).collect();
+        writeln!(out, "{} {sitelen}: {gloss}", lasina.join(" "))?;
+    }
+
+    Ok(!found.is_empty())
+}
+
+fn list_words(out: &mut impl io::Write, args: &Args) -> io::Result<()> {
+    let words = Word::ALL.iter().filter(|word| {
+        args.book.is_none_or(|book| word.book() == book)
+            && args.status.is_none_or(|status| word.status() == status)
+            && args.ucsur_version.is_none_or(|version| word.ucsur_version() == version)
+    });
+
+    for word in words {
+        writeln!(
+            out,
+            "{} {} {} {} {}",
+            word.as_lasina(),
+            word.as_sitelen(),
+            word.book().as_str(),
+            word.ucsur_version(),
+            word.status().as_str(),
+        )?;
     }
 
     Ok(())
 }
 
-// returns true when it should parse lasina to sitelen
-// return false when it should parse sitelen to lasina
-fn parse_args() -> bool {
+fn coverage(out: &mut impl io::Write, font: &Font) -> io::Result<()> {
+    let missing: Vec<&str> = Word::ALL
+        .iter()
+        .filter(|word| !font.supports(word.as_sitelen()))
+        .map(|word| word.as_lasina())
+        .collect();
+
+    writeln!(out, "words: {}/{}", Word::ALL.len() - missing.len(), Word::ALL.len())?;
+    if !missing.is_empty() {
+        writeln!(out, "  missing: {}", missing.join(" "))?;
+    }
+
+    let missing: Vec<(char, &str)> = UNICODE_TABLE
+        .into_iter()
+        .zip(UNICODE_NAMES)
+        .filter(|&(c, _)| !font.supports(c))
+        .collect();
+
+    writeln!(out, "modifiers: {}/{}", UNICODE_TABLE.len() - missing.len(), UNICODE_TABLE.len())?;
+    for (c, name) in missing {
+        writeln!(out, "  missing: U+{:X} {name}", c as u32)?;
+    }
+
+    let sequences: Vec<String> = Word::ALL
+        .iter()
+        .flat_map(|word| VARIATION_SELECTORS.map(move |selector| (word, selector)))
+        .filter(|&(word, selector)| font.supports_variation(word.as_sitelen(), selector))
+        .map(|(word, selector)| format!("{}+U+{:X}", word.as_lasina(), selector as u32))
+        .collect();
+
+    writeln!(out, "variation sequences: {}", sequences.len())?;
+    if !sequences.is_empty() {
+        writeln!(out, "  {}", sequences.join(" "))?;
+    }
+
+    Ok(())
+}
+
+fn parse_args() -> Args {
     let mut args = env::args();
     let program = args.next().unwrap_or_default();
 
-    let Some(opt) = args.next() else {
-        // if no arg is provided, by default try to parse lasina to sitelen
-        return true;
-    };
+    if let Some(output) = parse_options(args) {
+        let Err(message) = check_conflicts(&output) else {
+            return output;
+        };
 
-    match opt.as_str() {
-        "from" => return true,
-        "to" => return false,
-        _ => {},
+        eprintln!("error: {message}");
     }
 
     print_usage(&program);
     std::process::exit(1)
 }
 
+// the options that change how the output is written, which can't be combined with each other
+fn output_options(args: &Args) -> Vec<&'static str> {
+    let options = [
+        ("--width", args.width.is_some()),
+        ("--vertical", args.vertical.is_some()),
+        ("--gloss", args.gloss),
+        ("--ligatures", args.ligatures.is_some()),
+        ("--emosi", args.emosi),
+        ("--braille", args.braille),
+    ];
+
+    options.iter().filter(|(_, used)| *used).map(|(option, _)| *option).collect()
+}
+
+// the options that only some of the modes use, see `uses_option`
+const MODE_OPTIONS: [&str; 7] = ["--strict", "--words", "--fallback", "--numbers", "--book", "--status", "--ucsur"];
+
+// whether the mode, with the output options that were given, uses the option
+fn uses_option(args: &Args, option: &str) -> bool {
+    match (option, &args.mode) {
+        ("--strict", Mode::LasinaToSitelen) => true,
+
+        // braille is written from the tokens themselves
+        ("--words" | "--fallback" | "--numbers", Mode::LasinaToSitelen) => !args.braille,
+
+        // the words of the set are only written as they are with the ligatures, and the
+        // fallback is only reported by `from`
+        ("--words", Mode::SitelenToLasina) => args.ligatures.is_some(),
+        ("--fallback", Mode::SitelenToLasina) => {
+            args.ligatures.is_some() && args.style.fallback != Fallback::Error
+        }
+
+        // sitelen Emosi has no numbers
+        ("--numbers", Mode::SitelenToLasina) => !args.emosi,
+
+        ("--book" | "--status" | "--ucsur", Mode::Words) => true,
+        _ => false,
+    }
+}
+
+// options that conflict with each other, or that would otherwise be ignored without a word
+fn check_conflicts(args: &Args) -> Result<(), String> {
+    if let Some(option) = args.mode_options.iter().find(|option| !uses_option(args, option)) {
+        let options = output_options(args);
+        return Err(match options.first() {
+            Some(output) => format!("`{option}` isn't used by `{} {output}`", args.mode.as_str()),
+            None => format!("`{option}` isn't used by `{}`", args.mode.as_str()),
+        });
+    }
+
+    let options = output_options(args);
+    if let [first, second, ..] = options[..] {
+        return Err(format!("`{first}` can't be combined with `{second}`"));
+    }
+
+    match (&args.mode, &args.format) {
+        (Mode::LasinaToSitelen, Format::Text) => {}
+        (Mode::LasinaToSitelen, Format::Interlinear) if options.iter().all(|&o| o == "--gloss") => {}
+        (Mode::LasinaToSitelen, _) if !options.is_empty() => {
+            return Err(format!("`{}` only works with `--format text`", options[0]));
+        }
+        (Mode::LasinaToSitelen, _) => {}
+        (Mode::SitelenToLasina, format) => {
+            if let Some(option) = options
+                .iter()
+                .find(|&&o| !matches!(o, "--ligatures" | "--emosi" | "--braille"))
+            {
+                return Err(format!("`{option}` only works with `from`"));
+            }
+
+            if !matches!(format, Format::Text) {
+                return Err("`--format` only works with `from`".to_string());
+            }
+        }
+        (_, format) => {
+            if let Some(option) = options.first() {
+                return Err(format!("`{option}` only works with `from` or `to`"));
+            }
+
+            if !matches!(format, Format::Text) {
+                return Err("`--format` only works with `from`".to_string());
+            }
+        }
+    }
+
+    // the source map is of the plain sitelen UCSUR output, exactly as it's written
+    if args.source_map.is_some() {
+        if !matches!((&args.mode, &args.format), (Mode::LasinaToSitelen, Format::Text)) {
+            return Err("`--source-map` only works with `from --format text`".to_string());
+        }
+
+        if let Some(option) = options.first() {
+            return Err(format!("`--source-map` can't be combined with `{option}`"));
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Option<Args> {
+    // if no arg is provided, by default try to parse lasina to sitelen
+    let mut output = Args {
+        mode: Mode::LasinaToSitelen,
+        format: Format::Text,
+        style: Style::default(),
+        strict: false,
+        source_map: None,
+        width: None,
+        vertical: None,
+        direction: Direction::default(),
+        gloss: false,
+        book: None,
+        status: None,
+        ucsur_version: None,
+        font: None,
+        ligatures: None,
+        emosi: false,
+        braille: false,
+        mode_options: Vec::new(),
+    };
+
+    while let Some(opt) = args.next() {
+        if let Some(&option) = MODE_OPTIONS.iter().find(|&&option| option == opt) {
+            output.mode_options.push(option);
+        }
+
+        match opt.as_str() {
+            "from" => output.mode = Mode::LasinaToSitelen,
+            "to" => output.mode = Mode::SitelenToLasina,
+            "lint" => output.mode = Mode::Lint,
+            "check" => output.mode = Mode::Check,
+            "normalize" => output.mode = Mode::Normalize,
+            "define" => output.mode = Mode::Define(args.next()?),
+            "search" => output.mode = Mode::Search(args.next()?),
+            "words" => output.mode = Mode::Words,
+            "coverage" => output.mode = Mode::Coverage,
+            "--punctuation" => output.style.punctuation = args.next()?.parse().ok()?,
+            "--spacing" => output.style.spacing = args.next()?.parse().ok()?,
+            "--numbers" => output.style.numbers = Some(args.next()?.parse().ok()?),
+            "--words" => output.style.words = args.next()?.parse().ok()?,
+            "--fallback" => output.style.fallback = args.next()?.parse().ok()?,
+            "--strict" => output.strict = true,
+            "--gloss" => output.gloss = true,
+            "--book" => output.book = Some(args.next()?.parse().ok()?),
+            "--status" => output.status = Some(args.next()?.parse().ok()?),
+            "--font" => output.font = Some(args.next()?),
+            "--emosi" => output.emosi = true,
+            "--braille" => output.braille = true,
+            "--dialect" => output.style.dialect = Some(args.next()?.parse().ok()?),
+            "--ligatures" => output.ligatures = Some(args.next()?.parse().ok()?),
+            "--ucsur" => output.ucsur_version = Some(args.next()?.parse().ok()?),
+            "--format" => {
+                output.format = match args.next()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "jsonl" => Format::JsonLines,
+                    "interlinear" => Format::Interlinear,
+                    "html" => Format::Html,
+                    _ => return None,
+                }
+            }
+            "--source-map" => output.source_map = Some(args.next()?),
+            "--width" => output.width = Some(args.next()?.parse().ok()?),
+            "--vertical" => {
+                // a column is at least a glyph high
+                output.vertical = Some(args.next()?.parse().ok().filter(|&height| height > 0)?)
+            }
+            "--direction" => output.direction = args.next()?.parse().ok()?,
+            _ => return None,
+        }
+    }
+
+    Some(output)
+}
+
 fn print_usage(program: &str) {
-    eprintln!("Usage: {program} [from | to]")
+    eprintln!("Usage: {program} [from | to | lint | check | normalize | define <word> | search <english> | words | coverage] [OPTIONS]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --punctuation <middle-dot | ideographic | ascii | fullwidth>");
+    eprintln!("  --spacing <double | none | ideographic | zero-width | ascii>");
+    eprintln!("  --numbers <pu | pona>");
+    eprintln!("  --words <pu | ku-suli | ku-lili | ucsur-<year> | all>");
+    eprintln!("  --fallback <cartouche | lasina | error>");
+    eprintln!("  --font <path>");
+    eprintln!("  --ligatures <nasin-nanpa | linja-sike | linja-pona>");
+    eprintln!("  --emosi");
+    eprintln!("  --braille");
+    eprintln!("  --dialect <nasin-nanpa | linja-sike | linja-pona>");
+    eprintln!("  --strict");
+    eprintln!("  --format <text | json | jsonl | interlinear | html>");
+    eprintln!("  --source-map <path>");
+    eprintln!("  --width <columns>");
+    eprintln!("  --vertical <height>");
+    eprintln!("  --direction <rtl | ltr>");
+    eprintln!("  --gloss");
+    eprintln!("  --book <pu | ku-suli | ku-lili | none>");
+    eprintln!("  --status <core | common | uncommon>");
+    eprintln!("  --ucsur <year>");
 }
@@ -19,10 +19,33 @@ pub enum Modifier {
 }
 
 impl Modifier {
+    pub const ALL: [Modifier; 11] = [
+        Self::StartOfCartouche,
+        Self::EndOfCartouche,
+        Self::StackingJoiner,
+        Self::ScalingJoiner,
+        Self::StartOfLongGlyph,
+        Self::EndOfLongGlyph,
+        Self::CombiningLongGlyphExtension,
+        Self::StartOfReverseLongGlyph,
+        Self::EndOfReverseLongGlyph,
+        Self::MiddleDot,
+        Self::Colon,
+    ];
+
     // returns the unicode representation of the modifier
     pub const fn as_sitelen(self) -> char {
         UNICODE_TABLE[self as usize]
     }
+
+    // the unicode name of the modifier, without the `SITELEN PONA` prefix
+    pub const fn name(self) -> &'static str {
+        UNICODE_NAMES[self as usize]
+    }
+
+    pub fn from_sitelen(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|modifier| modifier.as_sitelen() == c)
+    }
 }
 
 pub const UNICODE_TABLE: [char; 14] = [
@@ -41,3 +64,41 @@ pub const UNICODE_TABLE: [char; 14] = [
     '\u{f199c}',
     '\u{f199d}',
 ];
+
+// the unicode names of the characters of `UNICODE_TABLE`, without the `SITELEN PONA` prefix,
+// including the ones that aren't used
+pub const UNICODE_NAMES: [&str; 14] = [
+    "START OF CARTOUCHE",
+    "END OF CARTOUCHE",
+    "COMBINING CARTOUCHE EXTENSION",
+    "START OF LONG PI",
+    "COMBINING LONG PI EXTENSION",
+    "STACKING JOINER",
+    "SCALING JOINER",
+    "START OF LONG GLYPH",
+    "END OF LONG GLYPH",
+    "COMBINING LONG GLYPH EXTENSION",
+    "START OF REVERSE LONG GLYPH",
+    "END OF REVERSE LONG GLYPH",
+    "MIDDLE DOT",
+    "COLON",
+];
+
+// VARIATION SELECTOR-1 to VARIATION SELECTOR-16, which select alternative forms of glyphs
+pub const VARIATION_SELECTORS: std::ops::RangeInclusive<char> = '\u{fe00}'..='\u{fe0f}';
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_codepoint_has_a_name() {
+        assert_eq!(Modifier::StackingJoiner.name(), "STACKING JOINER");
+        assert_eq!(Modifier::Colon.name(), "COLON");
+        assert_eq!(UNICODE_NAMES[3], "START OF LONG PI");
+
+        for modifier in Modifier::ALL {
+            assert_eq!(Modifier::from_sitelen(modifier.as_sitelen()), Some(modifier));
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::io;
+
+use crate::convert::Encoder;
+use crate::lexer::Token;
+use crate::modifier::VARIATION_SELECTORS;
+use crate::show::Style;
+use crate::spacing::Spacing;
+
+// the style of the canonical form, which doesn't depend on the options of the conversion. every
+// run of spaces between words is a single U+3000 IDEOGRAPHIC SPACE, and the punctuation is the
+// default one
+fn canonical_style() -> Style {
+    Style {
+        spacing: Spacing::Ideographic,
+        ..Style::default()
+    }
+}
+
+// rewrites sitelen UCSUR into a single canonical form, where every glyph has at most one
+// variation selector, empty cartouches are removed, spaces at the start and the end of lines are
+// removed, and every kind of quotation marks, as well as `te` and `to` written as words, becomes
+// 「」
+pub fn normalize(out: &mut impl io::Write, input: &str) -> io::Result<()> {
+    let style = canonical_style();
+    let tokens = trim_spaces(remove_empty_cartouches(decode(input, style)));
+
+    let mut encoder = Encoder::new(style);
+    let mut iter = tokens.iter().peekable();
+    while let Some(&token) = iter.next() {
+        let output = encoder.encode(token, iter.peek().copied());
+        out.write_all(output.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn decode(input: &str, style: Style) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let text = &input[i..i + c.len_utf8()];
+
+        if VARIATION_SELECTORS.contains(&c) {
+            // only the first variation selector of a glyph means anything
+            if let Some(Token::Word(..)) = tokens.last() {
+                tokens.push(match Token::from_sitelen(c, style) {
+                    Some(token) => token,
+                    None => Token::Other(text),
+                });
+            }
+
+            continue;
+        }
+
+        if Spacing::is_space(c) {
+            tokens.push(Token::Space(" "));
+
+            continue;
+        }
+
+        // quotes that are written as words in sitelen Lasina
+        if c.is_ascii_alphabetic() {
+            let mut end = i + c.len_utf8();
+            while let Some(&(j, c)) = chars.peek()
+                && c.is_ascii_alphabetic()
+            {
+                end = j + c.len_utf8();
+                chars.next();
+            }
+
+            tokens.push(match &input[i..end] {
+                "te" => Token::Te,
+                "to" => Token::To,
+                word => Token::Other(word),
+            });
+
+            continue;
+        }
+
+        let token = match c {
+            '\r' | '\n' => Token::Newline(text),
+            '『' | '“' | '«' => Token::Te,
+            '』' | '”' | '»' => Token::To,
+            c => Token::from_sitelen(c, style).unwrap_or(Token::Other(text)),
+        };
+
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+fn remove_empty_cartouches(tokens: Vec<Token<'_>>) -> Vec<Token<'_>> {
+    let mut output: Vec<Token> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if token == Token::RBrack {
+            // everything since the start of the cartouche, which can only be spaces
+            let start = output.iter().rposition(|t| !matches!(t, Token::Space(..)));
+            if let Some(start) = start
+                && output[start] == Token::LBrack
+            {
+                output.truncate(start);
+                continue;
+            }
+        }
+
+        output.push(token);
+    }
+
+    output
+}
+
+// a single space between tokens, and none at the start and the end of lines
+fn trim_spaces(tokens: Vec<Token<'_>>) -> Vec<Token<'_>> {
+    let mut output: Vec<Token> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        match token {
+            Token::Space(..)
+                if matches!(output.last(), None | Some(Token::Space(..) | Token::Newline(..))) => {}
+            Token::Newline(..) if matches!(output.last(), Some(Token::Space(..))) => {
+                output.pop();
+                output.push(token);
+            }
+            _ => output.push(token),
+        }
+    }
+
+    if let Some(Token::Space(..)) = output.last() {
+        output.pop();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::modifier::Modifier;
+    use crate::punctuation::Punctuation;
+    use crate::word::Word;
+
+    const TOKI: char = Word::Toki.as_sitelen();
+    const PONA: char = Word::Pona.as_sitelen();
+    const START: char = Modifier::StartOfCartouche.as_sitelen();
+    const END: char = Modifier::EndOfCartouche.as_sitelen();
+
+    fn normalized(input: &str) -> String {
+        let mut output = Vec::new();
+        normalize(&mut output, input).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn sitelen(input: &str, style: Style) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, style, None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn quotes_become_corner_brackets() {
+        let expected = format!("「{TOKI}」");
+        assert_eq!(normalized(&format!("『{TOKI}』")), expected);
+        assert_eq!(normalized(&format!("“{TOKI}”")), expected);
+        assert_eq!(normalized(&format!("te {TOKI} to")), format!("「\u{3000}{TOKI}\u{3000}」"));
+
+        // only whole words are quotes
+        assert_eq!(normalized("ten tote"), "ten\u{3000}tote");
+    }
+
+    #[test]
+    fn spaces_are_canonical() {
+        assert_eq!(normalized(&format!(" {TOKI}  \u{3000}{PONA}\t\n")), format!("{TOKI}\u{3000}{PONA}\n"));
+        assert_eq!(normalized(&format!("{TOKI}\u{200b}{PONA}")), format!("{TOKI}\u{3000}{PONA}"));
+    }
+
+    #[test]
+    fn empty_cartouches_and_extra_selectors_are_removed() {
+        assert_eq!(normalized(&format!("{TOKI} {START} {END} {PONA}")), format!("{TOKI}\u{3000}{PONA}"));
+        assert_eq!(normalized(&format!("{TOKI}\u{fe00}\u{fe01}")), format!("{TOKI}\u{fe00}"));
+        assert_eq!(normalized("\u{fe00}a"), "a");
+    }
+
+    #[test]
+    fn the_style_of_the_input_doesnt_matter() {
+        let text = "jan  [Sonja] li toki e ni: te toki, pona! to.\n";
+        // every one of them keeps the spaces between the words
+        let styles = [
+            Style {
+                spacing: Spacing::Ideographic,
+                ..Style::default()
+            },
+            Style {
+                spacing: Spacing::Ascii,
+                punctuation: Punctuation::Ideographic,
+                ..Style::default()
+            },
+            Style {
+                spacing: Spacing::ZeroWidth,
+                punctuation: Punctuation::Fullwidth,
+                ..Style::default()
+            },
+        ];
+
+        let expected = normalized(&sitelen(text, styles[1]));
+        for style in styles {
+            assert_eq!(normalized(&sitelen(text, style)), expected, "{style:?}");
+        }
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let inputs = [
+            format!(" te {TOKI}\u{fe00}\u{fe01} {START}{END}  to \n\n{PONA}.a"),
+            format!("{START} {END} {START}{TOKI}{END}\u{3000}\u{200b}, 『{PONA}』"),
+            sitelen("jan [Sonja] li toki-pona e (toki_ala). ni li 12!\n", Style::default()),
+            "toki te to ten\r\n".to_string(),
+        ];
+
+        for input in inputs {
+            let once = normalized(&input);
+            assert_eq!(normalized(&once), once, "{input:?}");
+        }
+    }
+}
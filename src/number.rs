@@ -0,0 +1,194 @@
+use crate::word::Word;
+
+// the systems for writing numbers with toki pona words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberSystem {
+    // additive, as described in pu: ale = 100, mute = 20, luka = 5, tu = 2, wan = 1
+    Pu,
+
+    // nasin nanpa pona: groups of base 100 separated by ale. a group of zero is left empty, and
+    // so is a leading group of one, e.g. ale = 100, ale wan = 101, tu ale = 200
+    Pona,
+}
+
+const ADDITIVE: [(Word, u64); 5] = [
+    (Word::Ale, 100),
+    (Word::Mute, 20),
+    (Word::Luka, 5),
+    (Word::Tu, 2),
+    (Word::Wan, 1),
+];
+
+// pu numbers are additive, so anything above this would be more than a hundred glyphs long
+const PU_MAX: u64 = 100 * 100;
+
+impl NumberSystem {
+    // returns `None` when the number is too big to be written in this system
+    pub fn to_words(self, mut number: u64) -> Option<Vec<Word>> {
+        if number == 0 {
+            return Some(vec![Word::Ala]);
+        }
+
+        Some(match self {
+            Self::Pu if number > PU_MAX => return None,
+            Self::Pu => additive(number, &ADDITIVE),
+            Self::Pona => {
+                let mut groups = Vec::new();
+                while number > 0 {
+                    groups.push(number % 100);
+                    number /= 100;
+                }
+
+                let leading = groups.len() - 1;
+                let mut output = Vec::new();
+                for (i, group) in groups.into_iter().rev().enumerate() {
+                    if i > 0 {
+                        output.push(Word::Ale);
+                    }
+
+                    if i == 0 && group == 1 && leading > 0 {
+                        continue;
+                    }
+
+                    // ale is the separator, so the group is only made out of the rest
+                    output.extend(additive(group, &ADDITIVE[1..]));
+                }
+
+                output
+            }
+        })
+    }
+
+    // returns `None` when the words don't form a valid number in this system
+    pub fn parse_words(self, words: &[Word]) -> Option<u64> {
+        if words == [Word::Ala] {
+            return Some(0);
+        }
+
+        match self {
+            Self::Pu => sum(words, &ADDITIVE),
+            Self::Pona if words.is_empty() => None,
+            Self::Pona => words.split(|&word| word == Word::Ale).enumerate().try_fold(
+                0u64,
+                |acc, (i, group)| {
+                    let group = match group {
+                        [] if i == 0 => 1,
+                        [] | [Word::Ala] => 0,
+                        _ => sum(group, &ADDITIVE[1..]).filter(|&value| value < 100)?,
+                    };
+
+                    acc.checked_mul(100)?.checked_add(group)
+                },
+            ),
+        }
+    }
+
+    pub fn is_number_word(self, word: Word) -> bool {
+        word == Word::Ala || ADDITIVE.iter().any(|&(w, _)| w == word)
+    }
+}
+
+fn additive(mut number: u64, values: &[(Word, u64)]) -> Vec<Word> {
+    let mut output = Vec::new();
+    for &(word, value) in values {
+        while number >= value {
+            output.push(word);
+            number -= value;
+        }
+    }
+
+    output
+}
+
+// the sum of the values of the words, where every word must be a number word.
+// an empty list is not a number.
+fn sum(words: &[Word], values: &[(Word, u64)]) -> Option<u64> {
+    if words.is_empty() {
+        return None;
+    }
+
+    words.iter().try_fold(0u64, |acc, word| {
+        let (_, value) = values.iter().find(|(w, _)| w == word)?;
+        acc.checked_add(*value)
+    })
+}
+
+impl std::str::FromStr for NumberSystem {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "pu" => Ok(Self::Pu),
+            "pona" => Ok(Self::Pona),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Word::{Ala, Ale, Luka, Mute, Tu, Wan};
+
+    const PU: [(u64, &[Word]); 6] = [
+        (0, &[Ala]),
+        (1, &[Wan]),
+        (20, &[Mute]),
+        (100, &[Ale]),
+        (101, &[Ale, Wan]),
+        (200, &[Ale, Ale]),
+    ];
+
+    const PONA: [(u64, &[Word]); 8] = [
+        (0, &[Ala]),
+        (1, &[Wan]),
+        (20, &[Mute]),
+        (100, &[Ale]),
+        (101, &[Ale, Wan]),
+        (200, &[Tu, Ale]),
+        (10000, &[Ale, Ale]),
+        (10100, &[Ale, Wan, Ale]),
+    ];
+
+    #[test]
+    fn to_words_of_pu() {
+        for (number, words) in PU {
+            assert_eq!(NumberSystem::Pu.to_words(number).as_deref(), Some(words), "{number}");
+        }
+    }
+
+    #[test]
+    fn to_words_of_pona() {
+        for (number, words) in PONA {
+            assert_eq!(NumberSystem::Pona.to_words(number).as_deref(), Some(words), "{number}");
+        }
+    }
+
+    #[test]
+    fn parse_words_of_pu() {
+        for (number, words) in PU {
+            assert_eq!(NumberSystem::Pu.parse_words(words), Some(number), "{words:?}");
+        }
+    }
+
+    #[test]
+    fn parse_words_of_pona() {
+        for (number, words) in PONA {
+            assert_eq!(NumberSystem::Pona.parse_words(words), Some(number), "{words:?}");
+        }
+
+        // groups of zero can also be written as ala
+        assert_eq!(NumberSystem::Pona.parse_words(&[Wan, Ale, Ala]), Some(100));
+    }
+
+    #[test]
+    fn parse_words_rejects_invalid_numbers() {
+        assert_eq!(NumberSystem::Pu.parse_words(&[]), None);
+        assert_eq!(NumberSystem::Pona.parse_words(&[]), None);
+
+        // a group of base 100 can't be 100 or more
+        assert_eq!(NumberSystem::Pona.parse_words(&[Mute; 5]), None);
+        assert_eq!(NumberSystem::Pona.parse_words(&[Luka, Word::Jan]), None);
+    }
+}
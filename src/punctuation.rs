@@ -0,0 +1,183 @@
+use crate::modifier::Modifier;
+
+// sentence punctuation, as opposed to the middle dot and colon used for names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Punct {
+    FullStop,
+    Comma,
+    Exclamation,
+    Question,
+}
+
+impl Punct {
+    // accepts every form that any of the policies produces
+    pub const fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            '.' | '。' | '．' => Self::FullStop,
+            ',' | '、' | '，' => Self::Comma,
+            '!' | '！' => Self::Exclamation,
+            '?' | '？' => Self::Question,
+            _ => return None,
+        })
+    }
+
+    pub const fn as_ascii(self) -> &'static str {
+        match self {
+            Self::FullStop => ".",
+            Self::Comma => ",",
+            Self::Exclamation => "!",
+            Self::Question => "?",
+        }
+    }
+}
+
+// how sentence punctuation is written in sitelen UCSUR
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Punctuation {
+    // full stop becomes SITELEN PONA MIDDLE DOT, everything else is kept as ASCII
+    #[default]
+    MiddleDot,
+
+    // 。、！？
+    Ideographic,
+
+    // .,!?
+    Ascii,
+
+    // ．，！？
+    Fullwidth,
+}
+
+impl Punctuation {
+    pub const fn as_sitelen(self, punct: Punct) -> char {
+        match (self, punct) {
+            (Self::MiddleDot, Punct::FullStop) => Modifier::MiddleDot.as_sitelen(),
+            (Self::MiddleDot | Self::Ascii, Punct::Comma) => ',',
+            (Self::MiddleDot | Self::Ascii, Punct::Exclamation) => '!',
+            (Self::MiddleDot | Self::Ascii, Punct::Question) => '?',
+            (Self::Ascii, Punct::FullStop) => '.',
+            (Self::Ideographic, Punct::FullStop) => '。',
+            (Self::Ideographic, Punct::Comma) => '、',
+            (Self::Fullwidth, Punct::FullStop) => '．',
+            (Self::Fullwidth, Punct::Comma) => '，',
+            (Self::Ideographic | Self::Fullwidth, Punct::Exclamation) => '！',
+            (Self::Ideographic | Self::Fullwidth, Punct::Question) => '？',
+        }
+    }
+
+    pub fn decode(self, c: char) -> Option<Punct> {
+        Punct::from_char(c).or_else(|| {
+            (self == Self::MiddleDot && c == Modifier::MiddleDot.as_sitelen())
+                .then_some(Punct::FullStop)
+        })
+    }
+}
+
+impl std::str::FromStr for Punctuation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "middle-dot" => Ok(Self::MiddleDot),
+            "ideographic" => Ok(Self::Ideographic),
+            "ascii" => Ok(Self::Ascii),
+            "fullwidth" => Ok(Self::Fullwidth),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::{from_sitelen, to_sitelen};
+    use crate::lexer::{self, Token};
+    use crate::show::Style;
+    use crate::word::Word;
+
+    const POLICIES: [Punctuation; 4] = [
+        Punctuation::MiddleDot,
+        Punctuation::Ideographic,
+        Punctuation::Ascii,
+        Punctuation::Fullwidth,
+    ];
+
+    const PUNCTS: [Punct; 4] = [Punct::FullStop, Punct::Comma, Punct::Exclamation, Punct::Question];
+
+    fn round_trip(input: &str, punctuation: Punctuation) -> String {
+        let style = Style {
+            punctuation,
+            ..Style::default()
+        };
+
+        let mut sitelen = Vec::new();
+        to_sitelen(&mut sitelen, input, style, None).unwrap();
+
+        let mut lasina = Vec::new();
+        from_sitelen(&mut lasina, &String::from_utf8(sitelen).unwrap(), style).unwrap();
+        String::from_utf8(lasina).unwrap()
+    }
+
+    // the tokens without the spaces, which `from_sitelen` writes after every token
+    fn tokens(input: &str) -> Vec<Token<'_>> {
+        lexer::tokens(input).filter(|token| !matches!(token, Token::Space(..))).collect()
+    }
+
+    #[test]
+    fn every_policy_decodes_what_it_writes() {
+        assert_eq!(POLICIES.map(|policy| policy.as_sitelen(Punct::FullStop)), ['\u{f199c}', '。', '.', '．']);
+        assert_eq!(POLICIES.map(|policy| policy.as_sitelen(Punct::Comma)), [',', '、', ',', '，']);
+
+        for policy in POLICIES {
+            for punct in PUNCTS {
+                assert_eq!(policy.decode(policy.as_sitelen(punct)), Some(punct), "{policy:?} {punct:?}");
+            }
+
+            // every form of the other policies is read too
+            assert_eq!(policy.decode('？'), Some(Punct::Question));
+            assert_eq!(policy.decode('a'), None);
+        }
+
+        assert_eq!("fullwidth".parse(), Ok(Punctuation::Fullwidth));
+        assert_eq!("dot".parse::<Punctuation>(), Err(()));
+    }
+
+    #[test]
+    fn middle_dot_is_only_a_full_stop_with_its_policy() {
+        let dot = Modifier::MiddleDot.as_sitelen();
+        let style = |punctuation| Style {
+            punctuation,
+            ..Style::default()
+        };
+
+        assert_eq!(Token::from_sitelen(dot, style(Punctuation::MiddleDot)), Some(Token::Punct(Punct::FullStop)));
+        assert_eq!(Token::from_sitelen(dot, style(Punctuation::Ascii)), Some(Token::Dot));
+        assert_eq!(Punctuation::Ideographic.decode(dot), None);
+    }
+
+    #[test]
+    fn dots_that_end_sentences() {
+        // a dot is only punctuation before a space or at the end
+        assert_eq!(tokens("toki."), tokens("toki ."));
+        assert_eq!(tokens("toki.\n"), [Token::Word(Word::Toki), Token::Punct(Punct::FullStop), Token::Newline("\n")]);
+        assert_eq!(tokens("toki. a")[1], Token::Punct(Punct::FullStop));
+        assert_eq!(tokens("toki.a")[1], Token::Dot);
+
+        // the others are always punctuation, and the colon never is
+        for input in ["seme?", "seme? a", "seme?\n", "a!", "a! a", "a!\n"] {
+            assert!(matches!(tokens(input)[1], Token::Punct(..)), "{input:?}");
+        }
+
+        for input in ["mi:", "mi: a", "mi:\n"] {
+            assert_eq!(tokens(input)[1], Token::Colon, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let input = "sina seme? a! mi, sina: pona.\nmi pona.\n";
+        for policy in POLICIES {
+            assert_eq!(tokens(&round_trip(input, policy)), tokens(input), "{policy:?}");
+        }
+    }
+}
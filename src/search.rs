@@ -0,0 +1,181 @@
+use crate::word::Word;
+
+// common phrases that mean something that no single word does
+const COMPOUNDS: &[(&[Word], &str)] = &[
+    (&[Word::Jan, Word::Pona], "friend"),
+    (&[Word::Jan, Word::Lili], "child, kid"),
+    (&[Word::Jan, Word::Olin], "lover, partner, sweetheart"),
+    (&[Word::Jan, Word::Sona], "teacher, expert, scientist"),
+    (&[Word::Jan, Word::Pali], "worker, employee"),
+    (&[Word::Jan, Word::Utala], "soldier, warrior, fighter"),
+    (&[Word::Jan, Word::Sewi], "god, deity"),
+    (&[Word::Jan, Word::Ala], "nobody, no one"),
+    (&[Word::Mama, Word::Mije], "father, dad"),
+    (&[Word::Mama, Word::Meli], "mother, mom"),
+    (&[Word::Mije, Word::Lili], "boy"),
+    (&[Word::Meli, Word::Lili], "girl"),
+    (&[Word::Tomo, Word::Tawa], "car, vehicle"),
+    (&[Word::Tomo, Word::Telo], "bathroom, toilet"),
+    (&[Word::Tomo, Word::Sona], "school, university"),
+    (&[Word::Tomo, Word::Moku], "restaurant, kitchen"),
+    (&[Word::Ma, Word::Tomo], "city, town"),
+    (&[Word::Telo, Word::Suli], "ocean, sea, lake"),
+    (&[Word::Telo, Word::Nasa], "alcohol, beer, wine"),
+    (&[Word::Telo, Word::Sewi], "rain"),
+    (&[Word::Telo, Word::Lete], "ice, cold water"),
+    (&[Word::Kon, Word::Wawa], "wind, storm"),
+    (&[Word::Ilo, Word::Toki], "phone, telephone"),
+    (&[Word::Ilo, Word::Sitelen], "pen, pencil, printer"),
+    (&[Word::Ilo, Word::Suno], "lamp, flashlight"),
+    (&[Word::Lipu, Word::Sona], "textbook, encyclopedia"),
+    (&[Word::Tenpo, Word::Suno], "day, daytime"),
+    (&[Word::Tenpo, Word::Pimeja], "night, nighttime"),
+    (&[Word::Tenpo, Word::Ni], "now, currently"),
+    (&[Word::Tenpo, Word::Kama], "future"),
+    (&[Word::Tenpo, Word::Pini], "past"),
+    (&[Word::Pona, Word::Lukin], "beautiful, pretty"),
+];
+
+// how well a meaning matches what was searched for, worst first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Quality {
+    // the search is the start of a word of the meaning
+    Prefix,
+
+    // the search is made out of whole words of the meaning
+    Word,
+
+    // the search is the whole meaning
+    Exact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Found {
+    pub words: &'static [Word],
+    pub gloss: &'static str,
+    pub quality: Quality,
+}
+
+// the words and phrases that have a meaning matching the English search, best match first.
+// matches of the same quality are ranked by how early the meaning is in the gloss, then single
+// words before phrases
+pub fn search(english: &str) -> Vec<Found> {
+    let english = english.trim().to_lowercase();
+    if english.is_empty() {
+        return Vec::new();
+    }
+
+    let words = Word::ALL
+        .iter()
+        .map(|word| (std::slice::from_ref(word), word.gloss()));
+
+    let mut found: Vec<(Quality, usize, Found)> = words
+        .chain(COMPOUNDS.iter().copied())
+        .filter_map(|(words, gloss)| {
+            let (index, quality) = gloss
+                .split(", ")
+                .enumerate()
+                .filter_map(|(index, meaning)| Some((index, quality(&english, meaning)?)))
+                .max_by_key(|&(index, quality)| (quality, std::cmp::Reverse(index)))?;
+
+            let found = Found { words, gloss, quality };
+            Some((quality, index, found))
+        })
+        .collect();
+
+    // stable, so the dictionary order is kept for the rest
+    found.sort_by_key(|&(quality, index, found)| {
+        (std::cmp::Reverse(quality), index, found.words.len())
+    });
+
+    found.into_iter().map(|(.., found)| found).collect()
+}
+
+fn quality(english: &str, meaning: &str) -> Option<Quality> {
+    // explanations in parentheses aren't meanings
+    let meaning = meaning.split(" (").next().unwrap_or_default().to_lowercase();
+    if meaning.is_empty() || meaning.starts_with('(') {
+        return None;
+    }
+
+    if meaning == english {
+        return Some(Quality::Exact);
+    }
+
+    let meaning: Vec<&str> = meaning.split(|c: char| !c.is_alphanumeric()).collect();
+    let search: Vec<&str> = english.split(|c: char| !c.is_alphanumeric()).collect();
+
+    if meaning.windows(search.len()).any(|words| words == search) {
+        return Some(Quality::Word);
+    }
+
+    // only the last word of the search can be unfinished, and it has to be long enough to not
+    // match most of the dictionary
+    let (last, rest) = search.split_last()?;
+    if last.len() < 2 {
+        return None;
+    }
+
+    let prefix_matches = meaning.windows(search.len()).any(|words| {
+        let (word, words) = words.split_last().expect("windows are never empty");
+        words == rest && word.starts_with(last)
+    });
+
+    prefix_matches.then_some(Quality::Prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(english: &str) -> Vec<(Vec<&'static str>, Quality)> {
+        search(english)
+            .into_iter()
+            .map(|found| (found.words.iter().map(|word| word.as_lasina()).collect(), found.quality))
+            .collect()
+    }
+
+    #[test]
+    fn best_match_first() {
+        assert_eq!(found("water"), [(vec!["telo"], Quality::Exact), (vec!["telo", "lete"], Quality::Word)]);
+        assert_eq!(found("person"), [(vec!["jan"], Quality::Exact)]);
+
+        // an exact meaning beats whole words of a meaning, even later in the gloss
+        assert_eq!(found("sea"), [(vec!["telo", "suli"], Quality::Exact), (vec!["kala"], Quality::Word)]);
+
+        // single words before phrases
+        assert_eq!(found("COLD"), [(vec!["lete"], Quality::Exact), (vec!["telo", "lete"], Quality::Word)]);
+    }
+
+    #[test]
+    fn unfinished_words() {
+        assert_eq!(found("fri"), [(vec!["jan", "pona"], Quality::Prefix)]);
+        assert_eq!(quality("cold wa", "cold water"), Some(Quality::Prefix));
+        assert_eq!(quality("wa", "cold water"), Some(Quality::Prefix));
+
+        // a single letter is too short, and only the start of a word matches
+        assert_eq!(quality("w", "water"), None);
+        assert_eq!(quality("ater", "water"), None);
+    }
+
+    #[test]
+    fn no_match() {
+        assert!(search("zzzz").is_empty());
+        assert!(search("  ").is_empty());
+
+        // explanations aren't meanings
+        assert!(found("subject").is_empty());
+    }
+
+    #[test]
+    fn compounds_are_phrases() {
+        for (words, gloss) in COMPOUNDS {
+            assert!(words.len() > 1, "{gloss}");
+            assert!(!gloss.is_empty() && !gloss.contains(" ("), "{gloss}");
+            assert_eq!(COMPOUNDS.iter().filter(|(other, _)| other == words).count(), 1, "{gloss}");
+        }
+
+        assert_eq!(found("friend"), [(vec!["jan", "pona"], Quality::Exact)]);
+        assert_eq!(found("mom"), [(vec!["mama", "meli"], Quality::Exact), (vec!["tenpo"], Quality::Prefix)]);
+    }
+}
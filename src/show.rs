@@ -1,12 +1,76 @@
 use std::fmt::{self, Write as _};
 
 use crate::lexer::Token;
+use crate::ligature::LigatureFont;
 use crate::modifier::Modifier;
-use crate::word::find_minimal_word_construction;
+use crate::number::NumberSystem;
+use crate::punctuation::Punctuation;
+use crate::spacing::Spacing;
+use crate::word::{Word, find_minimal_word_construction};
+use crate::word_set::{Fallback, WordSet};
+
+// policies for the parts of the text that have more than one way of being written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub punctuation: Punctuation,
+    pub spacing: Spacing,
+
+    // numbers are kept as digits when there's no number system
+    pub numbers: Option<NumberSystem>,
+
+    // the words that are written as glyphs, and how the rest of them are written
+    pub words: WordSet,
+    pub fallback: Fallback,
+
+    // the modifiers and quotes that are written as glyphs, and the words whose alternative forms
+    // are, e.g. the ones that a font has glyphs for
+    pub modifiers: ModifierSet,
+    pub alternatives: WordSet,
+
+    // the ligature font whose conventions are also accepted in the input
+    pub dialect: Option<LigatureFont>,
+}
+
+impl Style {
+    // whether the word is written in sitelen Lasina because it isn't in the word set, either by
+    // the fallback or because the words and the modifiers of the style aren't enough for spelling
+    // it
+    pub fn is_lasina(&self, word: Word) -> bool {
+        !self.words.contains(word)
+            && (self.fallback == Fallback::Lasina
+                || !self.modifiers.spells_names()
+                || find_minimal_word_construction(word.as_lasina(), self.words).is_none())
+    }
+
+    // the words that the number is written with, unless it's kept as digits because there's no
+    // number system, or any of its words isn't in the word set
+    pub fn number_words(&self, digits: &str) -> Option<Vec<Word>> {
+        let number = digits.parse().ok()?;
+        let words = self.numbers?.to_words(number)?;
+        words.iter().all(|&word| self.words.contains(word)).then_some(words)
+    }
+}
+
+pub struct Styled<'a> {
+    token: Token<'a>,
+    style: Style,
+}
+
+impl<'a> Token<'a> {
+    pub const fn styled(self, style: Style) -> Styled<'a> {
+        Styled { token: self, style }
+    }
+}
 
 impl<'a> fmt::Display for Token<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
+        fmt::Display::fmt(&self.styled(Style::default()), f)
+    }
+}
+
+impl<'a> fmt::Display for Styled<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.token {
             Token::LParen
             | Token::RParen
             | Token::LBrack
@@ -20,56 +84,91 @@ impl<'a> fmt::Display for Token<'a> {
             | Token::Colon
             | Token::Te
             | Token::To
-            | Token::AltSymbol => {
+            | Token::AltSymbol
+                if self.style.modifiers.contains(self.token) =>
+            {
                 let modifier = TOKEN_MODIFIER
                     .iter()
-                    .find(|(t, _)| t == self)
+                    .find(|(t, _)| *t == self.token)
                     .expect("modifier for token exists")
                     .1;
                 f.write_char(modifier)
             }
 
+            // the quotes are written in ASCII, and the rest of the modifiers as they were typed
+            Token::Te | Token::To => f.write_char('"'),
+            Token::AltSymbol => Ok(()),
+            Token::LParen
+            | Token::RParen
+            | Token::LBrack
+            | Token::RBrack
+            | Token::LBrace
+            | Token::RBrace
+            | Token::Plus
+            | Token::Minus
+            | Token::Underscore
+            | Token::Dot
+            | Token::Colon => f.write_str(self.token.as_literal()),
+
+            Token::Punct(punct) => f.write_char(self.style.punctuation.as_sitelen(*punct)),
+            Token::Word(word) if self.style.is_lasina(*word) => f.write_str(word.as_lasina()),
+            Token::Word(word) if !self.style.words.contains(*word) => {
+                f.write_char(Modifier::StartOfCartouche.as_sitelen())?;
+                construct_name(word.as_lasina(), self.style.words, f)?;
+                f.write_char(Modifier::EndOfCartouche.as_sitelen())
+            }
             Token::Word(word) => f.write_char(word.as_sitelen()),
+            Token::Lasina(s) if !self.style.modifiers.spells_names() => f.write_str(s),
             Token::Lasina(s) => {
                 f.write_char(Modifier::StartOfCartouche.as_sitelen())?;
-                construct_name(s, f)?;
+                construct_name(s, self.style.words, f)?;
                 f.write_char(Modifier::EndOfCartouche.as_sitelen())
             }
+            Token::Number(digits) => {
+                match self.style.number_words(digits) {
+                    Some(words) => words.iter().try_for_each(|word| f.write_char(word.as_sitelen())),
+                    None => f.write_str(digits),
+                }
+            }
             Token::Other(other) => f.write_str(other),
-            Token::Space(spaces) => f.write_str("\u{3000}".repeat(spaces.len() / 2).as_str()),
+            Token::Space(spaces) => self.style.spacing.write(spaces, f),
+            Token::Newline(newlines) => f.write_str(newlines),
         }
     }
 }
 
-fn construct_name(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
-    let Some(mut tokens) = find_minimal_word_construction(s) else {
+// the glyphs that spell the name inside of a cartouche, without the cartouche itself. only the
+// words of the set are used
+pub fn construct_name(s: &str, words: WordSet, f: &mut impl fmt::Write) -> fmt::Result {
+    let Some(mut tokens) = find_minimal_word_construction(s, words) else {
         return construct_name_simple(s, f);
     };
 
-    tokens.try_for_each(|token| fmt::Display::fmt(&token, f))
+    tokens.try_for_each(|token| write!(f, "{token}"))
+}
+
+fn construct_name_simple(s: &str, f: &mut impl fmt::Write) -> fmt::Result {
+    s.chars().try_for_each(|c| f.write_char(letter_glyph(c).unwrap_or(c)))
 }
 
-fn construct_name_simple(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
-    s.chars().try_for_each(|c| {
-        let c = match c.to_ascii_lowercase() {
-            'a' => '󱤆',
-            'e' => '󱤉',
-            'i' => '󱤏',
-            'j' => '󱤒',
-            'k' => '󱤕',
-            'l' => '󱤩',
-            'm' => '󱤱',
-            'n' => '󱥀',
-            'o' => '󱥇',
-            'p' => '󱥈',
-            's' => '󱥡',
-            't' => '󱥩',
-            'u' => '󱥱',
-            'w' => '󱥵',
-            c => c,
-        };
-
-        f.write_char(c)
+// the glyph that stands for the letter when names are spelled a letter at a time
+pub fn letter_glyph(c: char) -> Option<char> {
+    Some(match c.to_ascii_lowercase() {
+        'a' => '󱤆',
+        'e' => '󱤉',
+        'i' => '󱤏',
+        'j' => '󱤒',
+        'k' => '󱤕',
+        'l' => '󱤩',
+        'm' => '󱤱',
+        'n' => '󱥀',
+        'o' => '󱥇',
+        'p' => '󱥈',
+        's' => '󱥡',
+        't' => '󱥩',
+        'u' => '󱥱',
+        'w' => '󱥵',
+        _ => return None,
     })
 }
 
@@ -89,3 +188,43 @@ pub const TOKEN_MODIFIER: [(Token, char); 14] = [
     (Token::To, '」'),
     (Token::AltSymbol, '\u{fe00}'),
 ];
+
+// the tokens of `TOKEN_MODIFIER` that are written as their character, as a bit for each of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModifierSet(u16);
+
+impl Default for ModifierSet {
+    fn default() -> Self {
+        Self(u16::MAX)
+    }
+}
+
+impl ModifierSet {
+    // the tokens whose characters pass the check, e.g. the ones that a font has glyphs for. the
+    // alternative form depends on the glyph before it instead (see `Style.alternatives`), so it's
+    // always contained
+    pub fn only(supports: impl Fn(char) -> bool) -> Self {
+        let bits = TOKEN_MODIFIER
+            .iter()
+            .enumerate()
+            .filter(|(_, (token, c))| *token == Token::AltSymbol || supports(*c))
+            .fold(0, |bits, (i, _)| bits | 1 << i);
+
+        Self(bits)
+    }
+
+    // tokens that aren't modifiers are always contained
+    pub fn contains(self, token: Token) -> bool {
+        TOKEN_MODIFIER
+            .iter()
+            .position(|(t, _)| *t == token)
+            .is_none_or(|i| self.0 & 1 << i != 0)
+    }
+
+    // whether names can be spelled inside of cartouches, with middle dots and colons
+    pub fn spells_names(self) -> bool {
+        [Token::LBrack, Token::RBrack, Token::Dot, Token::Colon]
+            .into_iter()
+            .all(|token| self.contains(token))
+    }
+}
@@ -0,0 +1,175 @@
+use std::io;
+
+use crate::json;
+use crate::lexer::{Location, Span};
+
+// where a single token of the input ended up in the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    pub input: Span,
+
+    // byte offsets into the output, the end is exclusive. tokens that aren't written at all
+    // (e.g. spaces between glyphs) have an empty range
+    pub output_start: usize,
+    pub output_end: usize,
+}
+
+// maps positions in the sitelen Lasina input to positions in the sitelen UCSUR output and back
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    // sorted, and both sides are contiguous
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    // adds the next token, which must start where the previous one ended
+    pub fn push(&mut self, input: Span, output_len: usize) {
+        let output_start = self.output_end();
+        self.mappings.push(Mapping {
+            input,
+            output_start,
+            output_end: output_start + output_len,
+        })
+    }
+
+    // where the next token of the input starts
+    pub fn input_end(&self) -> Location {
+        self.mappings.last().map_or(Location::START, |mapping| mapping.input.end)
+    }
+
+    pub fn output_end(&self) -> usize {
+        self.mappings.last().map_or(0, |mapping| mapping.output_end)
+    }
+
+    // the mapping of the token that contains the given byte offset of the input
+    pub fn find_input(&self, offset: usize) -> Option<&Mapping> {
+        let index = self.mappings.partition_point(|mapping| mapping.input.end.offset <= offset);
+        self.mappings.get(index).filter(|mapping| mapping.input.start.offset <= offset)
+    }
+
+    // the mapping of the token that contains the given byte offset of the output
+    pub fn find_output(&self, offset: usize) -> Option<&Mapping> {
+        let index = self.mappings.partition_point(|mapping| mapping.output_end <= offset);
+        self.mappings.get(index).filter(|mapping| mapping.output_start <= offset)
+    }
+
+    pub fn write_json(&self, out: &mut impl io::Write) -> io::Result<()> {
+        write!(out, r#"{{"mappings":["#)?;
+        for (i, mapping) in self.mappings.iter().enumerate() {
+            if i > 0 {
+                write!(out, ",")?;
+            }
+
+            write!(out, r#"{{"input":"#)?;
+            json::write_span(out, mapping.input)?;
+            write!(
+                out,
+                r#","output":{{"start":{},"end":{}}}}}"#,
+                mapping.output_start, mapping.output_end,
+            )?;
+        }
+        writeln!(out, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::show::Style;
+
+    fn span(input: &str, start: usize, end: usize) -> Span {
+        Span {
+            start: Location::START.advance(&input[..start]),
+            end: Location::START.advance(&input[..end]),
+        }
+    }
+
+    // "toki pona" as three tokens, the space between the glyphs isn't written
+    fn toki_pona() -> SourceMap {
+        let input = "toki pona";
+        let mut map = SourceMap::default();
+        map.push(span(input, 0, 4), 4);
+        map.push(span(input, 4, 5), 0);
+        map.push(span(input, 5, 9), 4);
+        map
+    }
+
+    #[test]
+    fn push_follows_the_previous_token() {
+        let map = SourceMap::default();
+        assert_eq!(map.input_end(), Location::START);
+        assert_eq!(map.output_end(), 0);
+
+        let map = toki_pona();
+        let ranges: Vec<_> = map.mappings().iter().map(|mapping| (mapping.output_start, mapping.output_end)).collect();
+        assert_eq!(ranges, [(0, 4), (4, 4), (4, 8)]);
+        assert_eq!(map.input_end(), Location { offset: 9, line: 1, column: 10 });
+        assert_eq!(map.output_end(), 8);
+    }
+
+    #[test]
+    fn lookups_at_and_between_the_edges() {
+        let map = toki_pona();
+        let input = |offset| map.find_input(offset).map(|mapping| mapping.input.start.offset);
+        assert_eq!(input(0), Some(0));
+        assert_eq!(input(3), Some(0));
+        assert_eq!(input(4), Some(4));
+        assert_eq!(input(5), Some(5));
+        assert_eq!(input(8), Some(5));
+        assert_eq!(input(9), None);
+
+        // the empty range of the space is never found in the output
+        let output = |offset| map.find_output(offset).map(|mapping| mapping.input.start.offset);
+        assert_eq!(output(0), Some(0));
+        assert_eq!(output(3), Some(0));
+        assert_eq!(output(4), Some(5));
+        assert_eq!(output(7), Some(5));
+        assert_eq!(output(8), None);
+        assert_eq!(SourceMap::default().find_output(0), None);
+    }
+
+    #[test]
+    fn conversion_maps_every_token() {
+        let input = "mi\ntoki";
+        let mut output = Vec::new();
+        let mut map = SourceMap::default();
+        to_sitelen(&mut output, input, Style::default(), Some(&mut map)).unwrap();
+
+        assert_eq!(map.output_end(), output.len());
+        assert_eq!(map.input_end(), Location::START.advance(input));
+
+        // the glyph of toki is found from both sides
+        let toki = map.find_input(4).unwrap();
+        assert_eq!(toki.input.start, Location { offset: 3, line: 2, column: 1 });
+        assert_eq!(map.find_output(toki.output_start), Some(toki));
+    }
+
+    #[test]
+    fn json() {
+        let input = "a b";
+        let mut map = SourceMap::default();
+        map.push(span(input, 0, 1), 4);
+        map.push(span(input, 1, 2), 0);
+
+        let mut json = Vec::new();
+        map.write_json(&mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            concat!(
+                r#"{"mappings":["#,
+                r#"{"input":{"start":{"offset":0,"line":1,"column":1},"end":{"offset":1,"line":1,"column":2}},"output":{"start":0,"end":4}},"#,
+                r#"{"input":{"start":{"offset":1,"line":1,"column":2},"end":{"offset":2,"line":1,"column":3}},"output":{"start":4,"end":4}}"#,
+                "]}\n",
+            ),
+        );
+
+        let mut json = Vec::new();
+        SourceMap::default().write_json(&mut json).unwrap();
+        assert_eq!(json, b"{\"mappings\":[]}\n");
+    }
+}
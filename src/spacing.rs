@@ -0,0 +1,54 @@
+// how the spaces between words are written in sitelen UCSUR
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Spacing {
+    // a U+3000 IDEOGRAPHIC SPACE for every two spaces, so a single space between glyphs is dropped
+    // and a double space is kept. spaces next to text that isn't converted are kept as is, so it
+    // doesn't get glued to the glyphs around it
+    #[default]
+    Double,
+
+    // no spaces between glyphs, except next to text that isn't converted, like `Double`
+    None,
+
+    // a single U+3000 IDEOGRAPHIC SPACE for each run of spaces
+    Ideographic,
+
+    // a single U+200B ZERO WIDTH SPACE for each run of spaces, which allows line breaking
+    // without taking any room
+    ZeroWidth,
+
+    // spaces are kept exactly as they were typed
+    Ascii,
+}
+
+impl Spacing {
+    pub fn write(self, spaces: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Self::Double => (0..spaces.len() / 2).try_for_each(|_| f.write_char('\u{3000}')),
+            Self::None => Ok(()),
+            Self::Ideographic => f.write_char('\u{3000}'),
+            Self::ZeroWidth => f.write_char('\u{200b}'),
+            Self::Ascii => f.write_str(spaces),
+        }
+    }
+
+    // whether the given character is a space that any of the policies produces
+    pub const fn is_space(c: char) -> bool {
+        matches!(c, ' ' | '\t' | '\u{3000}' | '\u{200b}')
+    }
+}
+
+impl std::str::FromStr for Spacing {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "double" => Ok(Self::Double),
+            "none" => Ok(Self::None),
+            "ideographic" => Ok(Self::Ideographic),
+            "zero-width" => Ok(Self::ZeroWidth),
+            "ascii" => Ok(Self::Ascii),
+            _ => Err(()),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{self, Location, Spanned, Token};
+use crate::ligature::LigatureFont;
+use crate::suggest::did_you_mean;
+
+// rejects everything that would otherwise pass through the conversion silently
+#[derive(Debug, Default)]
+pub struct Strict {
+    // where the next line starts
+    location: Option<Location>,
+
+    // how deep inside explicit cartouches the current token is
+    cartouche_depth: usize,
+
+    dialect: Option<LigatureFont>,
+}
+
+impl Strict {
+    pub fn new(dialect: Option<LigatureFont>) -> Self {
+        Self {
+            dialect,
+            ..Self::default()
+        }
+    }
+
+    // checks the next line of the input
+    pub fn check(&mut self, input: &str) -> Vec<Diagnostic> {
+        let line_start = self.location.unwrap_or(Location::START);
+        self.location = Some(line_start.advance(input));
+
+        let mut diagnostics = Vec::new();
+        for Spanned { token, span } in lexer::spanned_tokens_in(input, line_start, self.dialect) {
+            let problem = match token {
+                Token::LBrack => {
+                    self.cartouche_depth += 1;
+                    None
+                }
+                Token::RBrack => {
+                    self.cartouche_depth = self.cartouche_depth.saturating_sub(1);
+                    None
+                }
+                Token::Lasina(word) if self.cartouche_depth == 0 => {
+                    Some(("unknown-word", format!("unknown word `{word}`"), did_you_mean(word)))
+                }
+                Token::Other(other) if other.chars().all(char::is_alphabetic) => {
+                    Some(("unknown-word", format!("unknown word `{other}`"), did_you_mean(other)))
+                }
+                Token::Other(other) if !is_punctuation(other) => {
+                    Some(("unexpected-text", format!("unexpected text `{other}`"), None))
+                }
+                _ => None,
+            };
+
+            if let Some((code, message, help)) = problem {
+                diagnostics.push(Diagnostic {
+                    code,
+                    span,
+                    message,
+                    help,
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn is_punctuation(text: &str) -> bool {
+    text.chars()
+        .all(|c| c.is_ascii_punctuation() || c.is_whitespace() || "«»‹›“”‘’„…–—".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(lines: &[&str]) -> Vec<(&'static str, String, Option<String>)> {
+        let mut strict = Strict::new(None);
+        lines
+            .iter()
+            .flat_map(|line| strict.check(line))
+            .map(|diagnostic| (diagnostic.code, diagnostic.span.start.to_string(), diagnostic.help))
+            .collect()
+    }
+
+    #[test]
+    fn unknown_words_are_reported_with_suggestions() {
+        assert_eq!(check(&["mi tokii\n"]), [(
+            "unknown-word",
+            "1:4".into(),
+            Some("did you mean `toki`?".into())
+        )]);
+    }
+
+    #[test]
+    fn names_inside_of_cartouches_are_allowed() {
+        assert_eq!(check(&["jan [sonja] li pona.\n"]), []);
+        assert_eq!(check(&["jan sonja\n"]).len(), 1);
+    }
+
+    #[test]
+    fn unexpected_text_is_reported() {
+        assert_eq!(check(&["toki 😀\n"]), [("unexpected-text", "1:6".into(), None)]);
+        assert_eq!(check(&["toki, pona!\n"]), []);
+    }
+
+    #[test]
+    fn locations_continue_across_lines() {
+        assert_eq!(check(&["toki\n", "mi tokii\n"])[0].1, "2:4");
+
+        // a cartouche that's still open carries over to the next line
+        assert_eq!(check(&["[jan\n", "sonja]\n"]), []);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::word::Word;
+
+// misspellings that the edit distance doesn't find, or doesn't rank first
+const MISSPELLINGS: &[(&str, Word)] = &[
+    ("ali", Word::Ale),
+    ("nampa", Word::Nanpa),
+    ("yan", Word::Jan),
+    ("yo", Word::Jo),
+    ("yelo", Word::Jelo),
+    ("yaki", Word::Jaki),
+    ("pimeya", Word::Pimeja),
+    ("sijelu", Word::Sijelo),
+    ("kijetesantakalo", Word::Kijetesantakalu),
+];
+
+const MAX_SUGGESTIONS: usize = 3;
+
+// dictionary words that the given word was probably meant to be, best match first
+pub fn suggestions(word: &str) -> Vec<Word> {
+    let word = word.to_lowercase();
+
+    let mut output: Vec<Word> = MISSPELLINGS
+        .iter()
+        .filter(|(misspelling, _)| *misspelling == word)
+        .map(|(_, suggestion)| *suggestion)
+        .collect();
+
+    // longer words are allowed to have more mistakes in them
+    let max_distance = (word.chars().count() / 4).max(1);
+
+    let mut candidates: Vec<(usize, Word)> = Word::ALL
+        .iter()
+        .map(|&candidate| (edit_distance(&word, candidate.as_lasina()), candidate))
+        .filter(|&(distance, candidate)| {
+            distance <= max_distance && candidate.as_lasina() != word && !output.contains(&candidate)
+        })
+        .collect();
+
+    // stable, so words with the same distance keep the dictionary order
+    candidates.sort_by_key(|&(distance, _)| distance);
+
+    output.extend(candidates.into_iter().map(|(_, candidate)| candidate));
+    output.truncate(MAX_SUGGESTIONS);
+    output
+}
+
+// the optimal string alignment distance, which is the Levenshtein distance where swapping two
+// adjacent letters also counts as a single edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // dp[i][j] is the distance between the first i letters of `a` and the first j letters of `b`
+    let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, value) in dp[0].iter_mut().enumerate() {
+        *value = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    dp[a.len()][b.len()]
+}
+
+// the two dictionary words that the given word is made out of, when the space between them was
+// left out, e.g. `tokipona`
+pub fn split(word: &str) -> Option<[Word; 2]> {
+    let word = word.to_lowercase();
+    (1..word.len()).filter(|&i| word.is_char_boundary(i)).find_map(|i| {
+        let (first, second) = word.split_at(i);
+        Some([first.parse().ok()?, second.parse().ok()?])
+    })
+}
+
+// the help text of a diagnostic about an unknown word, if there's anything to suggest
+pub fn did_you_mean(word: &str) -> Option<String> {
+    // capitalized words are most likely names
+    if word.starts_with(char::is_uppercase) {
+        return None;
+    }
+
+    let mut suggestions: Vec<String> = split(word)
+        .map(|[first, second]| format!("{} {}", first.as_lasina(), second.as_lasina()))
+        .into_iter()
+        .chain(suggestions(word).iter().map(|suggestion| suggestion.as_lasina().to_string()))
+        .collect();
+
+    suggestions.truncate(MAX_SUGGESTIONS);
+    let (last, rest) = suggestions.split_last()?;
+
+    let mut help = String::from("did you mean ");
+    for suggestion in rest {
+        help += &format!("`{suggestion}`, ");
+    }
+
+    if !rest.is_empty() {
+        help.truncate(help.len() - 2);
+        help += " or ";
+    }
+
+    help += &format!("`{last}`?");
+    Some(help)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typos_are_found_by_edit_distance() {
+        assert_eq!(suggestions("tokii").first(), Some(&Word::Toki));
+        assert_eq!(suggestions("pnoa").first(), Some(&Word::Pona));
+    }
+
+    #[test]
+    fn known_misspellings_come_first() {
+        assert_eq!(suggestions("yan").first(), Some(&Word::Jan));
+        assert_eq!(suggestions("pimeya").first(), Some(&Word::Pimeja));
+    }
+
+    #[test]
+    fn words_without_the_space_between_them() {
+        assert_eq!(split("tokipona"), Some([Word::Toki, Word::Pona]));
+        assert_eq!(split("Janpona"), Some([Word::Jan, Word::Pona]));
+        assert_eq!(split("tokii"), None);
+        assert_eq!(did_you_mean("tokipona").as_deref(), Some("did you mean `toki pona`?"));
+    }
+
+    #[test]
+    fn names_have_no_help() {
+        assert_eq!(did_you_mean("Tokii"), None);
+        assert_eq!(did_you_mean("xyzzyq"), None);
+        assert_eq!(did_you_mean("tokii").as_deref(), Some("did you mean `toki`?"));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::cluster::{Cluster, ClusterKind, clusters};
+use crate::modifier::{Modifier, VARIATION_SELECTORS};
+use crate::spacing::Spacing;
+use crate::word::Word;
+
+// the width of the text in terminal columns, where a glyph takes a full-width cell (two columns)
+pub fn width(text: &str) -> usize {
+    clusters(text).map(|cluster| cluster_width(&cluster)).sum()
+}
+
+pub fn cluster_width(cluster: &Cluster) -> usize {
+    match cluster.kind {
+        // a joined pair takes a single cell, no matter how many glyphs are in it
+        ClusterKind::Glyph | ClusterKind::Joined => 2 + extensions_width(cluster.text),
+
+        // the start and the end of the block are drawn around the glyphs inside of it, and middle
+        // dots and colons inside of names are drawn on top of the glyph before them
+        ClusterKind::Cartouche | ClusterKind::LongGlyph => {
+            let inner: String = cluster
+                .text
+                .chars()
+                .filter(|&c| {
+                    !Modifier::from_sitelen(c).is_some_and(|modifier| {
+                        !matches!(
+                            modifier,
+                            Modifier::StackingJoiner
+                                | Modifier::ScalingJoiner
+                                | Modifier::CombiningLongGlyphExtension
+                        )
+                    })
+                })
+                .collect();
+
+            clusters(&inner).map(|cluster| cluster_width(&cluster)).sum()
+        }
+
+        ClusterKind::Other => char_width(cluster.text),
+    }
+}
+
+// every COMBINING LONG GLYPH EXTENSION makes the glyph longer by a cell
+fn extensions_width(text: &str) -> usize {
+    let extension = Modifier::CombiningLongGlyphExtension.as_sitelen();
+    2 * text.chars().filter(|&c| c == extension).count()
+}
+
+// the width of a cluster of a single character, or a CRLF
+fn char_width(text: &str) -> usize {
+    let Some(c) = text.chars().next() else {
+        return 0;
+    };
+
+    match c {
+        _ if c.is_control() || VARIATION_SELECTORS.contains(&c) => 0,
+        '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => 0,
+        _ if Modifier::from_sitelen(c).is_some_and(|modifier| {
+            matches!(
+                modifier,
+                Modifier::StackingJoiner
+                    | Modifier::ScalingJoiner
+                    | Modifier::CombiningLongGlyphExtension
+            )
+        }) =>
+        {
+            0
+        }
+        _ if Word::from_sitelen(c).is_some() || Modifier::from_sitelen(c).is_some() => 2,
+        '\u{1100}'..='\u{115f}'
+        | '\u{2e80}'..='\u{303e}'
+        | '\u{3041}'..='\u{33ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{a000}'..='\u{a4cf}'
+        | '\u{ac00}'..='\u{d7a3}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe30}'..='\u{fe4f}'
+        | '\u{ff00}'..='\u{ff60}'
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f300}'..='\u{1f64f}'
+        | '\u{1f900}'..='\u{1f9ff}' => 2,
+        _ => 1,
+    }
+}
+
+// breaks the lines of the text so that none of them is wider than the given width. lines are
+// only broken between clusters, so cartouches and joined glyphs always stay whole, and a
+// cluster that is wider than the width by itself gets a line of its own
+pub fn wrap(text: &str, width: usize) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut line_width = 0;
+
+    // the spaces after the last cluster, which are dropped if the line is broken at them
+    let mut spaces = String::new();
+    let mut spaces_width = 0;
+
+    for cluster in clusters(text) {
+        if matches!(cluster.text, "\n" | "\r\n") {
+            output += &spaces;
+            output += cluster.text;
+            line_width = 0;
+            spaces.clear();
+            spaces_width = 0;
+            continue;
+        }
+
+        let cluster_width = cluster_width(&cluster);
+        if cluster.text.chars().all(Spacing::is_space) {
+            spaces += cluster.text;
+            spaces_width += cluster_width;
+            continue;
+        }
+
+        if line_width > 0 && line_width + spaces_width + cluster_width > width {
+            output.push('\n');
+            line_width = 0;
+        } else {
+            output += &spaces;
+            line_width += spaces_width;
+        }
+
+        spaces.clear();
+        spaces_width = 0;
+
+        output += cluster.text;
+        line_width += cluster_width;
+    }
+
+    output + &spaces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::to_sitelen;
+    use crate::show::Style;
+
+    fn sitelen(input: &str) -> String {
+        let mut output = Vec::new();
+        to_sitelen(&mut output, input, Style::default(), None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(width(&sitelen("toki pona")), 4);
+        assert_eq!(width(&sitelen("toki-pona")), 2);
+        assert_eq!(width(&sitelen("toki^")), 2);
+        assert_eq!(width("abc 語"), 6);
+
+        // the cartouche is drawn around the glyphs, and the colon on top of them
+        assert_eq!(width(&sitelen("[Sonja]")), 4);
+        assert_eq!(width(&sitelen("pi(toki ala)")), 6);
+    }
+
+    #[test]
+    fn lines_are_broken_at_the_width() {
+        let text = sitelen("mi wile toki");
+        let lines: Vec<_> = wrap(&text, 4).lines().map(width).collect();
+        assert_eq!(lines, [4, 2]);
+
+        // every line fits, and nothing but the spaces at the breaks is dropped
+        for limit in 1..8 {
+            let wrapped = wrap(&text, limit);
+            assert!(wrapped.lines().all(|line| width(line) <= limit.max(2)));
+            assert_eq!(wrapped.replace('\n', ""), text.replace('\u{3000}', ""));
+        }
+    }
+
+    #[test]
+    fn clusters_are_never_broken() {
+        let text = sitelen("jan [Sonja] li toki-pona");
+        for limit in 1..12 {
+            let wrapped = wrap(&text, limit);
+            let clusters: Vec<_> = clusters(&wrapped).map(|cluster| cluster.text).filter(|text| *text != "\n").collect();
+            let expected: Vec<_> = clusters_of(&text);
+            assert_eq!(clusters, expected, "width {limit}");
+        }
+
+        // a cluster wider than the limit gets a line of its own
+        assert_eq!(wrap(&text, 2).lines().count(), 4);
+    }
+
+    #[test]
+    fn line_breaks_are_kept() {
+        let text = sitelen("mi\ntoki");
+        assert_eq!(wrap(&text, 80), text);
+        assert_eq!(wrap("a\r\nb", 80), "a\r\nb");
+    }
+
+    fn clusters_of(text: &str) -> Vec<&str> {
+        clusters(text).map(|cluster| cluster.text).filter(|text| !text.chars().all(Spacing::is_space)).collect()
+    }
+}
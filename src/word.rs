@@ -1,6 +1,89 @@
 include!(concat!(env!("OUT_DIR"), "/word.rs"));
 
 use crate::lexer::Token;
+use crate::modifier::Modifier;
+use crate::word_set::WordSet;
+
+// the book that a word comes from, as listed by the linku dictionary (https://linku.la)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Book {
+    // Toki Pona: The Language of Good
+    Pu,
+
+    // the words of Toki Pona Dictionary that the most of the community uses
+    KuSuli,
+
+    // the rest of the words of Toki Pona Dictionary
+    KuLili,
+
+    // words that are in UCSUR but in neither of the books
+    None,
+}
+
+impl Book {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pu => "pu",
+            Self::KuSuli => "ku-suli",
+            Self::KuLili => "ku-lili",
+            Self::None => "none",
+        }
+    }
+}
+
+impl std::str::FromStr for Book {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "pu" => Ok(Self::Pu),
+            "ku-suli" => Ok(Self::KuSuli),
+            "ku-lili" => Ok(Self::KuLili),
+            "none" => Ok(Self::None),
+            _ => Err(()),
+        }
+    }
+}
+
+// how much a word is used nowadays, from the usage categories of linku
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Core,
+    Common,
+    Uncommon,
+}
+
+impl Status {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::Common => "common",
+            Self::Uncommon => "uncommon",
+        }
+    }
+}
+
+impl std::str::FromStr for Status {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "core" => Ok(Self::Core),
+            "common" => Ok(Self::Common),
+            "uncommon" => Ok(Self::Uncommon),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Word {
+    // the first of the meanings without its explanation, short enough to be written under the
+    // glyph
+    pub fn short_gloss(self) -> &'static str {
+        let gloss = self.gloss().split(", ").next().unwrap_or_default();
+        gloss.split(" (").next().unwrap_or_default()
+    }
+}
 
 enum Section {
     FullWord(Word),
@@ -15,6 +98,14 @@ impl Section {
         }
     }
 
+    // the word whose glyph is written for the section
+    fn word(&self) -> Word {
+        match self {
+            Self::FullWord(word) => *word,
+            Self::Dots(text, ..) => SECTION_TO_WORD[text],
+        }
+    }
+
     fn weight(&self) -> u32 {
         match self {
             Self::FullWord(..) => 2,
@@ -23,8 +114,11 @@ impl Section {
     }
 }
 
-pub fn find_minimal_word_construction(word: &str) -> Option<impl Iterator<Item = Token<'static>>> {
-    find_minimal_word_construction_sections(word).map(|sections| {
+pub fn find_minimal_word_construction(
+    word: &str,
+    words: WordSet,
+) -> Option<impl Iterator<Item = Token<'static>>> {
+    find_minimal_word_construction_sections(word, words).map(|sections| {
         sections.into_iter().flat_map(|section| match section {
             Section::FullWord(word) => {
                 std::iter::once(Token::Word(*word)).chain(std::iter::repeat_n(Token::Colon, 1))
@@ -41,7 +135,44 @@ pub fn find_minimal_word_construction(word: &str) -> Option<impl Iterator<Item =
     })
 }
 
-fn find_minimal_word_construction_sections(word: &str) -> Option<Vec<&'static Section>> {
+// the inverse of `find_minimal_word_construction`: reads the name that the glyphs inside of a
+// cartouche spell. returns `None` when there's anything else inside of the cartouche, or when
+// there are only glyphs, without any middle dots or colons, since that's a cartouche of words
+// (e.g. `[toki pona]`), which is kept as it is
+pub fn read_name(glyphs: &str) -> Option<String> {
+    let marks = [Modifier::Colon.as_sitelen(), Modifier::MiddleDot.as_sitelen()];
+    if !glyphs.contains(marks) {
+        return None;
+    }
+
+    let mut name = String::new();
+    let mut chars = glyphs.chars().peekable();
+    while let Some(c) = chars.next() {
+        let word = Word::from_sitelen(c)?;
+
+        if chars.next_if_eq(&Modifier::Colon.as_sitelen()).is_some() {
+            name += word.as_lasina();
+            continue;
+        }
+
+        let mut dots = 0;
+        while chars.next_if_eq(&Modifier::MiddleDot.as_sitelen()).is_some() {
+            dots += 1;
+        }
+
+        name += word.prefix(dots)?;
+    }
+
+    // names are capitalized
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+fn find_minimal_word_construction_sections(
+    word: &str,
+    words: WordSet,
+) -> Option<Vec<&'static Section>> {
     let word = word.to_lowercase();
     let word = word.as_str();
 
@@ -72,7 +203,7 @@ fn find_minimal_word_construction_sections(word: &str) -> Option<Vec<&'static Se
             continue;
         }
 
-        for section in SECTIONS {
+        for section in SECTIONS.iter().filter(|section| words.contains(section.word())) {
             let section_text = section.text();
             let next_index = index + section_text.len();
 
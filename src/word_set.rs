@@ -0,0 +1,139 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{self, Location, Spanned, Token};
+use crate::show::Style;
+use crate::word::{Book, Word};
+
+// the number of `u64`s that have a bit for every word of the dictionary
+const BLOCKS: usize = Word::ALL.len().div_ceil(64);
+
+// the words that are written as glyphs, e.g. the words that a font has glyphs for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordSet {
+    Pu,
+
+    // the words of pu and ku suli
+    KuSuli,
+
+    // the words of pu, ku suli and ku lili
+    KuLili,
+
+    // the words that were in the UCSUR version of the given year
+    Ucsur(u16),
+
+    // the given words, as a bit for every word, e.g. the words that a font has glyphs for
+    Only([u64; BLOCKS]),
+
+    #[default]
+    All,
+}
+
+impl WordSet {
+    pub fn only(words: impl IntoIterator<Item = Word>) -> Self {
+        let mut bits = [0; BLOCKS];
+        for word in words {
+            bits[word as usize / 64] |= 1 << (word as usize % 64);
+        }
+
+        Self::Only(bits)
+    }
+
+    pub fn contains(self, word: Word) -> bool {
+        match self {
+            Self::Pu => word.book() == Book::Pu,
+            Self::KuSuli => matches!(word.book(), Book::Pu | Book::KuSuli),
+            Self::KuLili => matches!(word.book(), Book::Pu | Book::KuSuli | Book::KuLili),
+            Self::Ucsur(year) => word.ucsur_version() <= year,
+            Self::Only(bits) => bits[word as usize / 64] & (1 << (word as usize % 64)) != 0,
+            Self::All => true,
+        }
+    }
+}
+
+impl std::str::FromStr for WordSet {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "pu" => Ok(Self::Pu),
+            "ku-suli" => Ok(Self::KuSuli),
+            "ku-lili" => Ok(Self::KuLili),
+            "all" => Ok(Self::All),
+            _ => {
+                let year = s.strip_prefix("ucsur-").ok_or(())?;
+                year.parse().map(Self::Ucsur).map_err(|_| ())
+            }
+        }
+    }
+}
+
+// how the words that aren't in the word set are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    // spelled inside of a cartouche with the words of the set, like a name
+    #[default]
+    Cartouche,
+
+    // in sitelen Lasina, as they were typed
+    Lasina,
+
+    // like `Cartouche`, but the words are also reported
+    Error,
+}
+
+impl std::str::FromStr for Fallback {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "cartouche" => Ok(Self::Cartouche),
+            "lasina" => Ok(Self::Lasina),
+            "error" => Ok(Self::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+// reports every word of the input, which starts at the given location, that isn't in the word
+// set of the style
+pub fn check(input: &str, start: Location, style: Style) -> Vec<Diagnostic> {
+    lexer::spanned_tokens_in(input, start, style.dialect)
+        .filter_map(|Spanned { token, span }| match token {
+            Token::Word(word) if !style.words.contains(word) => Some(Diagnostic {
+                code: "word-not-in-set",
+                span,
+                message: format!("`{}` isn't in the word set", word.as_lasina()),
+                help: None,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_has_a_bit_for_every_word() {
+        let words: Vec<Word> = Word::ALL.iter().copied().skip(1).step_by(2).collect();
+        let set = WordSet::only(words.iter().copied());
+        for &word in Word::ALL {
+            assert_eq!(set.contains(word), words.contains(&word), "{}", word.as_lasina());
+        }
+
+        assert!(WordSet::only(Word::ALL.iter().copied()).contains(*Word::ALL.last().unwrap()));
+        assert!(!WordSet::only([]).contains(Word::A));
+    }
+
+    #[test]
+    fn books_and_versions() {
+        assert!(WordSet::Pu.contains(Word::Toki));
+        assert!(!WordSet::Pu.contains(Word::Kijetesantakalu));
+        assert!(WordSet::KuSuli.contains(Word::Kijetesantakalu));
+        assert!(!WordSet::KuSuli.contains(Word::Linluwi));
+        assert!(WordSet::KuLili.contains(Word::Linluwi));
+        assert!(!WordSet::Ucsur(2023).contains(Word::Linluwi));
+        assert_eq!("ucsur-2024".parse(), Ok(WordSet::Ucsur(2024)));
+        assert_eq!("ucsur-".parse::<WordSet>(), Err(()));
+    }
+}
@@ -0,0 +1,48 @@
+use sitelen_ucsur::emosi::{from_emosi, to_emosi};
+use sitelen_ucsur::show::Style;
+
+fn emosi(text: &str) -> String {
+    let mut output = Vec::new();
+    to_emosi(&mut output, text, Style::default()).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn lasina(text: &str) -> String {
+    let mut output = Vec::new();
+    from_emosi(&mut output, text).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let cases = [
+        "toki\n",
+        "jan Sonja li toki e toki pona\n",
+        "jan [toki pona] li pona\n",
+        "jan [Sonja] li lon\n",
+        "toki-pona li pona+ala\n",
+        "mi olin e sina a\nsina olin e mi\n",
+        "pi (toki ala) li {toki}\n",
+        "jan <3 li pona\n",
+    ];
+
+    for text in cases {
+        let expected = text.replace("[Sonja]", "Sonja");
+        assert_eq!(lasina(&emosi(text)), expected, "{text:?} as {:?}", emosi(text));
+    }
+}
+
+#[test]
+fn names_and_cartouches_of_words_are_told_apart() {
+    assert_ne!(emosi("jan Tp"), emosi("jan [toki pona]"));
+    assert_eq!(lasina(&emosi("jan Tp")), "jan Tp");
+
+    // a cartouche without the mark of a name is read as words
+    assert_eq!(lasina(&emosi("[toki pona]").replace("[_", "[")), "[toki pona]");
+}
+
+#[test]
+fn no_spaces_at_the_ends_of_lines() {
+    let output = lasina(&emosi("toki  pona \n  mi\n"));
+    assert!(output.lines().all(|line| !line.ends_with(' ')), "{output:?}");
+}
@@ -0,0 +1,207 @@
+use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+use sitelen_ucsur::convert::{from_sitelen, to_sitelen};
+use sitelen_ucsur::lexer::{self, Token};
+use sitelen_ucsur::number::NumberSystem;
+use sitelen_ucsur::show::Style;
+use sitelen_ucsur::word::{Word, find_minimal_word_construction};
+use sitelen_ucsur::word_set::WordSet;
+
+const CONSONANTS: &[char] = &['j', 'k', 'l', 'm', 'n', 'p', 's', 't', 'w'];
+const VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u'];
+
+// a sitelen Lasina input, together with what it's expected to be after a round trip
+#[derive(Debug, Clone)]
+struct Input {
+    text: String,
+    expected: String,
+}
+
+impl Input {
+    fn push(&mut self, text: &str, expected: &str) {
+        self.text += text;
+        self.text += " ";
+        self.expected += expected;
+        self.expected += " ";
+    }
+}
+
+fn word(g: &mut Gen) -> Word {
+    *g.choose(Word::ALL).unwrap()
+}
+
+// a name that is read back as itself, see `is_ambiguous`
+fn name(g: &mut Gen) -> String {
+    loop {
+        let syllables = usize::arbitrary(g) % 3 + 1;
+        let mut name = String::new();
+        for _ in 0..syllables {
+            name.push(*g.choose(CONSONANTS).unwrap());
+            name.push(*g.choose(VOWELS).unwrap());
+        }
+
+        // anything else would be read as a word
+        if name.parse::<Word>().is_err() && name != "te" && name != "to" && !is_ambiguous(&name) {
+            return name;
+        }
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    name[..1].to_uppercase() + &name[1..]
+}
+
+// whether the name is spelled with the first letters of words alone, without middle dots or
+// colons, which is the same as a cartouche of those words
+fn is_ambiguous(name: &str) -> bool {
+    find_minimal_word_construction(name, WordSet::All)
+        .unwrap()
+        .all(|token| !matches!(token, Token::Dot | Token::Colon))
+}
+
+// the cartouche of the words that the ambiguous name is spelled with
+fn cartouche(name: &str) -> String {
+    let words: Vec<&str> = find_minimal_word_construction(name, WordSet::All)
+        .unwrap()
+        .map(|token| token.as_literal())
+        .collect();
+
+    format!("[{}]", words.join(" "))
+}
+
+impl Arbitrary for Input {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut input = Input {
+            text: String::new(),
+            expected: String::new(),
+        };
+
+        for _ in 0..g.size() {
+            let (a, b) = (word(g).as_lasina(), word(g).as_lasina());
+            match u8::arbitrary(g) % 13 {
+                0..=2 => input.push(a, a),
+                3 => {
+                    let name = name(g);
+                    input.push(&name, &capitalize(&name));
+                }
+                4 => {
+                    let name = capitalize(&name(g));
+                    input.push(&format!("[{name}]"), &name);
+                }
+                5 => {
+                    let cartouche = format!("[{a} {b}]");
+                    input.push(&cartouche, &cartouche);
+                }
+                6 => {
+                    let joiner = g.choose(&["-", "+"]).unwrap();
+                    input.push(&format!("{a}{joiner}{b}"), &format!("{a} {joiner} {b}"));
+                }
+                7 => input.push(&format!("({a}_{b})"), &format!("( {a} _ {b} )")),
+                8 => input.push(&format!("{{{a}}}"), &format!("{{ {a} }}")),
+                9 => input.push(&format!("{a}^"), &format!("{a} ^")),
+                10 => input.push(&format!("te {a} to"), &format!("te {a} to")),
+                11 => {
+                    let punct = g.choose(&[".", ",", "!", "?", ":"]).unwrap();
+                    input.push(&format!("{a}{punct}"), &format!("{a} {punct}"));
+                }
+                _ => input.push("\n", "\n"),
+            }
+        }
+
+        input
+    }
+}
+
+// the tokens of the text, without the spaces between them
+fn tokens(text: &str) -> Vec<Token<'_>> {
+    lexer::tokens(text)
+        .filter(|token| !matches!(token, Token::Space(..)))
+        .collect()
+}
+
+fn round_trip(text: &str, style: Style) -> String {
+    let mut sitelen = Vec::new();
+    to_sitelen(&mut sitelen, text, style, None).unwrap();
+
+    let mut lasina = Vec::new();
+    from_sitelen(&mut lasina, &String::from_utf8(sitelen).unwrap(), style).unwrap();
+    String::from_utf8(lasina).unwrap()
+}
+
+#[test]
+fn round_trip_of_lasina() {
+    fn prop(input: Input) -> bool {
+        let output = round_trip(&input.text, Style::default());
+        tokens(&output) == tokens(&input.expected)
+    }
+
+    QuickCheck::new().tests(500).quickcheck(prop as fn(Input) -> bool);
+}
+
+#[test]
+fn round_trip_of_every_name_section() {
+    // every prefix that a name can be made out of has to be read back as itself
+    for &word in Word::ALL {
+        for dots in 0.. {
+            let Some(prefix) = word.prefix(dots) else {
+                break;
+            };
+
+            // capitalized, so it's a name even when the prefix is a word by itself
+            let name = capitalize(prefix);
+            let expected = if is_ambiguous(prefix) { cartouche(prefix) } else { name.clone() };
+            let output = round_trip(&name, Style::default());
+            assert_eq!(tokens(&output), tokens(&expected), "{word:?} with {dots} dots");
+        }
+    }
+}
+
+#[test]
+fn round_trip_of_numbers() {
+    fn prop(numbers: Vec<u16>, pu: bool) -> bool {
+        let system = if pu { NumberSystem::Pu } else { NumberSystem::Pona };
+        let style = Style {
+            numbers: Some(system),
+            ..Style::default()
+        };
+
+        let text: Vec<String> = numbers.iter().map(|number| number.to_string()).collect();
+        let text = text.join(" ");
+
+        let output = round_trip(&text, style);
+        tokens(&output) == tokens(&text)
+    }
+
+    QuickCheck::new().quickcheck(prop as fn(Vec<u16>, bool) -> bool);
+}
+
+#[test]
+fn round_trip_of_cartouches_of_words() {
+    for text in ["[toki pona]", "jan [jan]", "[mi en sina] li pona"] {
+        let output = round_trip(text, Style::default());
+        assert_eq!(tokens(&output), tokens(text), "{text}");
+    }
+
+    // a name is still read back as a name
+    assert_eq!(round_trip("jan [Sonja]", Style::default()).trim(), "jan Sonja");
+}
+
+#[test]
+fn round_trip_of_names() {
+    // spelled with the first letters of words alone, so they're read back as the cartouche of
+    // those words
+    for name in ["jiji", "jupe", "nuju"] {
+        assert!(is_ambiguous(name), "{name}");
+        let output = round_trip(&capitalize(name), Style::default());
+        assert_eq!(tokens(&output), tokens(&cartouche(name)), "{name}");
+    }
+
+    assert_eq!(cartouche("jipe"), "[jaki ijo pakala e]");
+
+    // every generated name is read back as itself
+    let mut g = Gen::new(10);
+    for _ in 0..200 {
+        let name = capitalize(&name(&mut g));
+        assert_eq!(round_trip(&format!("[{name}]"), Style::default()).trim(), name);
+    }
+}